@@ -7,9 +7,15 @@ mod cell;
 mod grid;
 use grid::Grid;
 
+mod solution_log;
+use solution_log::SolutionLog;
+
 mod solver_controls;
 use solver_controls::{Solver, SolverControls};
 
+mod strategy_list;
+use strategy_list::StrategyList;
+
 // =============================================================================
 
 pub type SolverHandle = UseReducerHandle<Solver>;
@@ -28,6 +34,8 @@ pub fn App() -> Html {
                 <Grid />
                 <div class={classes!("w-1/3", "flex", "flex-col", "items-start", "ml-10")}>
                     <SolverControls />
+                    <SolutionLog />
+                    <StrategyList />
                 </div>
             </ContextProvider<SolverHandle>>
         </div>
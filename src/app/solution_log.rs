@@ -0,0 +1,44 @@
+use yew::prelude::*;
+
+use super::SolverHandle;
+
+// =============================================================================
+
+#[function_component]
+pub fn SolutionLog() -> Html {
+    // get contexts ------------------------------------------------------------
+
+    let solver = use_context::<SolverHandle>().expect("Solver context not found");
+
+    // derive attributes -------------------------------------------------------
+
+    let path = solver.solution_path();
+
+    let steps = path.steps.iter().enumerate().map(|(i, step)| {
+        html! {
+            <div class={classes!("strategy-item")}>
+                <div class={classes!("strategy-number")}>{ i + 1 }</div>
+                <div class={classes!("strategy-name")}>{ step.strategy.name }</div>
+            </div>
+        }
+    });
+
+    let pending = solver.strategy.map(|strategy| {
+        html! {
+            <div class={classes!("strategy-item")}>
+                <div class={classes!("strategy-number")}>{ path.steps.len() + 1 }</div>
+                <div class={classes!("strategy-name")}>{ strategy.name }</div>
+                <div class={classes!("strategy-status")}>{ "?" }</div>
+            </div>
+        }
+    });
+
+    // render ------------------------------------------------------------------
+
+    html! {
+        <div class={classes!("strategy-list")}>
+            { for steps }
+            { for pending }
+        </div>
+    }
+}
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use yew::prelude::*;
 
-pub use crate::sudoku::{Solver, SolverAction};
+pub use crate::sudoku::{Generator, Solver, SolverAction};
 
 use super::SolverHandle;
 
@@ -26,16 +26,30 @@ pub fn SolverControls() -> Html {
         })
     };
 
+    let on_new: Callback<MouseEvent> = {
+        let solver = solver.clone();
+        Callback::from(move |_| {
+            let seed = (js_sys::Math::random() * u64::MAX as f64) as u64;
+
+            if let Some(puzzle) = Generator::new(seed).generate() {
+                solver.dispatch(SolverAction::LoadBoardString(puzzle.to_string()))
+            }
+        })
+    };
+
     let on_undo: Callback<MouseEvent> = {
         let solver = solver.clone();
         Callback::from(move |_| solver.dispatch(SolverAction::Undo))
     };
 
     let on_step: Callback<MouseEvent> = {
-        // let solver = solver.clone();
+        let solver = solver.clone();
         Callback::from(move |_| solver.dispatch(SolverAction::Step))
     };
 
+    let on_solve: Callback<MouseEvent> =
+        Callback::from(move |_| solver.dispatch(SolverAction::Solve));
+
     // render ------------------------------------------------------------------
 
     let button_classes = classes!("bg-light", "hover:bg-dark", "hover:text-light", "font-bold", "py-1", "px-2");
@@ -43,8 +57,10 @@ pub fn SolverControls() -> Html {
     html! {
         <div class={classes!("flex", "flex-row")}>
             <button class={button_classes.clone()} onclick={on_reset}>{"reset"}</button>
+            <button class={button_classes.clone()} onclick={on_new}>{"new"}</button>
             <button class={button_classes.clone()} onclick={on_undo}>{"undo"}</button>
             <button class={button_classes.clone()} onclick={on_step}>{"step"}</button>
+            <button class={button_classes.clone()} onclick={on_solve}>{"solve"}</button>
         </div>
     }
 }
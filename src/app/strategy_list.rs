@@ -0,0 +1,101 @@
+use yew::prelude::*;
+
+use crate::sudoku::{SolverAction, PROFILE_LIST};
+
+use super::SolverHandle;
+
+// =============================================================================
+
+#[function_component]
+pub fn StrategyList() -> Html {
+    // get contexts ------------------------------------------------------------
+
+    let solver = use_context::<SolverHandle>().expect("Solver context not found");
+
+    // derive attributes -------------------------------------------------------
+
+    let profiles = PROFILE_LIST.iter().map(|&profile| {
+        let on_click: Callback<MouseEvent> = {
+            let solver = solver.clone();
+            Callback::from(move |_| solver.dispatch(SolverAction::LoadProfile(profile)))
+        };
+
+        html! {
+            <button class={classes!("bg-light", "hover:bg-dark", "hover:text-light", "py-1", "px-2")}
+                onclick={on_click}
+            >
+                { profile.name }
+            </button>
+        }
+    });
+
+    let assume_unique = solver.assumes_unique();
+
+    let on_toggle_unique: Callback<MouseEvent> = {
+        let solver = solver.clone();
+        Callback::from(move |_| solver.dispatch(SolverAction::SetAssumeUnique(!assume_unique)))
+    };
+
+    let allow_trial = solver.allows_trial();
+
+    let on_toggle_trial: Callback<MouseEvent> = {
+        let solver = solver.clone();
+        Callback::from(move |_| solver.dispatch(SolverAction::SetAllowTrial(!allow_trial)))
+    };
+
+    let strategies = solver
+        .strategies()
+        .iter()
+        .enumerate()
+        .map(|(i, &strategy)| {
+            let enabled = solver.is_enabled(&strategy);
+
+            // clicking the number moves the strategy up one place
+            let on_move: Callback<MouseEvent> = {
+                let solver = solver.clone();
+                Callback::from(move |_| {
+                    solver.dispatch(SolverAction::MoveStrategy(i, i.saturating_sub(1)))
+                })
+            };
+
+            let on_toggle: Callback<MouseEvent> = {
+                let solver = solver.clone();
+                Callback::from(move |_| {
+                    solver.dispatch(SolverAction::SetStrategyEnabled(strategy, !enabled))
+                })
+            };
+
+            html! {
+                <div class={classes!("strategy-item")}>
+                    <div class={classes!("strategy-number")} onclick={on_move}>{ i + 1 }</div>
+                    <div class={classes!("strategy-name")}>{ strategy.name }</div>
+                    <div class={classes!("strategy-status")} onclick={on_toggle}>
+                        { if enabled { "✓" } else { "" } }
+                    </div>
+                </div>
+            }
+        });
+
+    // render ------------------------------------------------------------------
+
+    html! {
+        <div class={classes!("flex", "flex-col", "items-start")}>
+            <div class={classes!("flex", "flex-row")}>
+                { for profiles }
+                <button class={classes!("bg-light", "hover:bg-dark", "hover:text-light", "py-1", "px-2")}
+                    onclick={on_toggle_unique}
+                >
+                    { if assume_unique { "unique ✓" } else { "unique" } }
+                </button>
+                <button class={classes!("bg-light", "hover:bg-dark", "hover:text-light", "py-1", "px-2")}
+                    onclick={on_toggle_trial}
+                >
+                    { if allow_trial { "trial ✓" } else { "trial" } }
+                </button>
+            </div>
+            <div class={classes!("strategy-list")}>
+                { for strategies }
+            </div>
+        </div>
+    }
+}
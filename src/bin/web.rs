@@ -0,0 +1,9 @@
+use sudoku::app::App;
+
+// =============================================================================
+
+fn main() {
+    wasm_logger::init(wasm_logger::Config::default());
+
+    yew::Renderer::<App>::new().render();
+}
@@ -333,9 +333,10 @@ impl<E: Element, B: BitsRepr> BitSet<E, B> {
         self.bits = B::ZERO;
     }
 
-    /// returns `true` if the set contains the specified element.
+    /// returns `true` if the set contains the specified element. elements past
+    /// the end of the set's bits are never contained.
     pub fn contains(&self, element: E) -> bool {
-        self.bits & B::single(element.index()) != B::ZERO
+        element.index() < B::SIZE && self.bits & B::single(element.index()) != B::ZERO
     }
 
     /// returns `true` if the set is a subset of another,
@@ -591,6 +592,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn contains_out_of_range() {
+        let set: Set<Elt> = (0..8).map(Elt).collect();
+
+        // without the bounds check, this shifts past the end of the u8 and
+        // panics on overflow in debug builds
+        assert!(!set.contains(Elt(14)));
+    }
+
     #[test]
     fn subset() {
         let ps = (0..4)
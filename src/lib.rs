@@ -0,0 +1,17 @@
+//! Sudoku solver built around human-style solving strategies.
+//!
+//! The core types live in [`sudoku`]: a [`Board`] of cells holding either a
+//! digit or a set of candidate notes, a list of [`Strategy`]s which find
+//! deductions on a board, and a [`Solver`] which steps through a puzzle by
+//! applying them in order.
+//!
+//! The Yew frontend is available under the `web` feature as `app`.
+
+pub mod bitset;
+pub mod sudoku;
+pub mod util;
+
+#[cfg(feature = "web")]
+pub mod app;
+
+pub use sudoku::{Board, Solver, Strategy, StrategyResult, STRATEGY_LIST};
@@ -1,13 +1,271 @@
-mod app;
-mod sudoku;
-mod util;
+//! command-line solver.
+//!
+//! reads puzzles from a file, or from stdin if no file is given, one 81
+//! character puzzle string per line. blank lines and lines starting with `#`
+//! are skipped. each puzzle is run through the strategy list, or the subset
+//! given by `--profile`, until it is solved or no strategy applies, and a
+//! tab-separated line is printed:
+//!
+//! ```text
+//! <final grid>    <solved|stuck>    <grade>    <max>    <total>    <strategy> x<count>, ...
+//! ```
+//!
+//! where `<max>` and `<total>` are the highest and summed step difficulties the
+//! grade is based on.
+//!
+//! strategies which rely on the puzzle having a unique solution, such as unique
+//! rectangles, are only used with `--assume-unique`. trial and error is only
+//! used as a last resort with `--trial`.
+//!
+//! with `--verify`, every deduction is checked against the brute-force
+//! solution and any unsound one is reported on stderr.
+//!
+//! `sudoku generate` instead prints randomly generated puzzles in the same
+//! one-per-line format, so they can be piped straight back into the solver.
 
-mod bitset;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sudoku::{
+    sudoku::{Generator, Grade, Profile, Symmetry, Violation, PROFILE_LIST},
+    Solver,
+};
 
 // =============================================================================
 
-fn main() {
-    wasm_logger::init(wasm_logger::Config::default());
+const USAGE: &str = "\
+usage: sudoku [--verify] [--assume-unique] [--trial] [--profile NAME] [FILE]
+       sudoku generate [OPTIONS]
+
+reads puzzles from FILE, or stdin if FILE is omitted or `-`
+
+options:
+    --verify            check every deduction against the puzzle's unique solution
+    --assume-unique     allow strategies which are only sound for unique puzzles
+    --trial             fall back to trial and error when logic gets stuck
+    --profile NAME      only use the strategies in a profile: default,
+                        singles-only, basic or no-chains
+
+generate options:
+    --count N           number of puzzles to generate (default 1)
+    --seed N            seed for the random generator (default from the clock)
+    --symmetry NAME     none, rotational, quarter, horizontal, vertical or diagonal
+    --grade NAME        easy, medium, hard, unfair or extreme
+    --attempts N        puzzles to try per requested grade (default 100)";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match args.split_first() {
+        Some((command, rest)) if command == "generate" => generate(rest),
+        _ => solve(args),
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+fn solve(args: Vec<String>) -> ExitCode {
+    let mut verify = false;
+    let mut assume_unique = false;
+    let mut allow_trial = false;
+    let mut profile = PROFILE_LIST[0];
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verify" => verify = true,
+            "--assume-unique" => assume_unique = true,
+            "--trial" => allow_trial = true,
+            "--profile" => {
+                let Some(name) = args.next() else {
+                    eprintln!("error: missing value for {arg}\n\n{USAGE}");
+                    return ExitCode::FAILURE;
+                };
+
+                let Some(named) = Profile::from_name(name) else {
+                    eprintln!("error: unknown profile {name}");
+                    return ExitCode::FAILURE;
+                };
+
+                profile = named;
+            }
+            _ if arg.starts_with("--") => {
+                eprintln!("error: unknown option {arg}\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+
+    let input: Box<dyn BufRead> = match paths.as_slice() {
+        [] => Box::new(io::stdin().lock()),
+        ["-"] => Box::new(io::stdin().lock()),
+        [path] => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(err) => {
+                eprintln!("error: could not open {path}: {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut output = BufWriter::new(io::stdout().lock());
+    let mut had_error = false;
+
+    for (line_number, line) in input.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("error: could not read input: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let puzzle = line.trim();
+
+        if puzzle.is_empty() || puzzle.starts_with('#') {
+            continue;
+        }
+
+        if puzzle.chars().count() != 81 {
+            eprintln!(
+                "error: line {}: expected 81 characters, found {}",
+                line_number + 1,
+                puzzle.chars().count()
+            );
+            had_error = true;
+            continue;
+        }
+
+        let (report, violations) =
+            solve_puzzle(puzzle, &profile, verify, assume_unique, allow_trial);
+
+        for violation in &violations {
+            eprintln!("error: line {}: {violation}", line_number + 1);
+            had_error = true;
+        }
+
+        if writeln!(output, "{report}").is_err() {
+            // stdout was closed, e.g. piped into `head`
+            return ExitCode::SUCCESS;
+        }
+    }
+
+    if had_error {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn solve_puzzle(
+    puzzle: &str,
+    profile: &Profile,
+    verify: bool,
+    assume_unique: bool,
+    allow_trial: bool,
+) -> (String, Vec<Violation>) {
+    let mut solver = Solver::from_profile(profile);
+
+    solver.set_verify(verify);
+    solver.set_assume_unique(assume_unique);
+    solver.set_allow_trial(allow_trial);
+    solver.load_board_string(puzzle);
+
+    let path = solver.solve();
+
+    let status = if path.is_solved() { "solved" } else { "stuck" };
+
+    let strategies = path
+        .strategy_counts()
+        .iter()
+        .map(|(strategy, count)| format!("{} x{count}", strategy.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let rating = path.rating();
+
+    let report = format!(
+        "{}\t{status}\t{}\t{}\t{}\t{strategies}",
+        path.board, rating.grade, rating.max_difficulty, rating.total_difficulty
+    );
+
+    (report, solver.violations)
+}
+
+// -----------------------------------------------------------------------------
+
+fn generate(args: &[String]) -> ExitCode {
+    let mut count = 1;
+    let mut seed = None;
+    let mut symmetry = Symmetry::None;
+    let mut grade = None;
+    let mut attempts = 100;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            eprintln!("error: missing value for {arg}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        };
+
+        let parsed = match arg.as_str() {
+            "--count" => value.parse().map(|n| count = n).is_ok(),
+            "--seed" => value.parse().map(|n| seed = Some(n)).is_ok(),
+            "--symmetry" => value.parse().map(|s| symmetry = s).is_ok(),
+            "--grade" => value.parse::<Grade>().map(|g| grade = Some(g)).is_ok(),
+            "--attempts" => value.parse().map(|n| attempts = n).is_ok(),
+            _ => {
+                eprintln!("error: unknown option {arg}\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if !parsed {
+            eprintln!("error: invalid value for {arg}: {value}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    });
+
+    let mut generator = Generator::new(seed);
+    generator.symmetry = symmetry;
+    generator.grade = grade;
+    generator.max_attempts = attempts;
+
+    let mut output = BufWriter::new(io::stdout().lock());
+
+    for _ in 0..count {
+        let Some(puzzle) = generator.generate() else {
+            eprintln!("error: no puzzle found with the requested grade");
+            return ExitCode::FAILURE;
+        };
+
+        if writeln!(output, "{puzzle}").is_err() {
+            return ExitCode::SUCCESS;
+        }
+    }
 
-    yew::Renderer::<app::App>::new().render();
+    ExitCode::SUCCESS
 }
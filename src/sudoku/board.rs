@@ -1,3 +1,5 @@
+use std::fmt;
+
 use log::error;
 
 use crate::bitset::{Element, Set};
@@ -6,9 +8,12 @@ use super::{Candidate, Cell, Digit};
 
 // =============================================================================
 
+/// contents of a single cell: either a placed digit or the remaining notes
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CellData {
+    /// cell has been solved with this digit
     Digit(Digit),
+    /// cell is unsolved, with these digits as candidates
     Notes(Set<Digit>),
 }
 
@@ -20,18 +25,27 @@ impl Default for CellData {
 
 // =============================================================================
 
+/// state of all 81 cells of a sudoku grid
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Board([CellData; 81]);
 
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
 impl Board {
     // constructors ------------------------------------------------------------
 
+    /// creates a board where every cell has all nine notes.
     pub fn new() -> Board {
         Board([CellData::default(); 81])
     }
 
     // cell getters ------------------------------------------------------------
 
+    /// returns the contents of `cell`.
     pub fn get_data(&self, cell: &Cell) -> &CellData {
         self.0
             .get(cell.index())
@@ -44,6 +58,7 @@ impl Board {
             .unwrap_or_else(|| panic!("Cell {cell} not found in board"))
     }
 
+    /// returns the digit in `cell`, if it is solved.
     pub fn get_digit(&self, cell: &Cell) -> Option<Digit> {
         match self.get_data(cell) {
             CellData::Digit(digit) => Some(*digit),
@@ -51,6 +66,7 @@ impl Board {
         }
     }
 
+    /// returns the notes in `cell`, if it is unsolved.
     pub fn get_notes(&self, cell: &Cell) -> Option<&Set<Digit>> {
         match self.get_data(cell) {
             CellData::Digit(_) => None,
@@ -67,14 +83,17 @@ impl Board {
 
     // cell checkers -----------------------------------------------------------
 
+    /// returns `true` if `cell` is solved.
     pub fn is_digit(&self, cell: &Cell) -> bool {
         matches!(self.get_data(cell), CellData::Digit(_))
     }
 
+    /// returns `true` if `cell` is unsolved.
     pub fn is_notes(&self, cell: &Cell) -> bool {
         matches!(self.get_data(cell), CellData::Notes(_))
     }
 
+    /// returns `true` if `cell` is unsolved and has `digit` as a note.
     pub fn has_note(&self, cell: &Cell, digit: Digit) -> bool {
         match self.get_notes(cell) {
             Some(notes) => notes.contains(digit),
@@ -82,6 +101,7 @@ impl Board {
         }
     }
 
+    /// returns the number of notes in `cell`, or zero if it is solved.
     pub fn count_notes(&self, cell: &Cell) -> usize {
         match self.get_notes(cell) {
             Some(notes) => notes.len(),
@@ -89,23 +109,37 @@ impl Board {
         }
     }
 
+    /// returns `true` if every cell is solved.
+    pub fn is_solved(&self) -> bool {
+        Cell::list().all(|cell| self.is_digit(&cell))
+    }
+
     // mutators ----------------------------------------------------------------
 
+    /// resets every cell to have all nine notes.
     pub fn clear(&mut self) {
         for ref cell in Cell::list() {
             *self.get_data_mut(cell) = CellData::default();
         }
     }
 
+    /// places `digit` in `cell`, discarding its notes.
     pub fn set_digit(&mut self, cell: Cell, digit: Digit) {
         *self.get_data_mut(&cell) = CellData::Digit(digit);
     }
 
+    /// resets `cell` to have all nine notes.
+    pub fn clear_cell(&mut self, cell: Cell) {
+        *self.get_data_mut(&cell) = CellData::default();
+    }
+
+    /// places the digit of `candidate` in its cell.
     pub fn input_solution(&mut self, candidate: Candidate) {
         let (cell, digit) = candidate.as_tuple();
         self.set_digit(cell, digit);
     }
 
+    /// removes `candidate` from the notes of its cell.
     pub fn input_elimination(&mut self, candidate: Candidate) {
         let (cell, digit) = candidate.as_tuple();
 
@@ -123,33 +157,56 @@ impl Board {
 
     // iterators ---------------------------------------------------------------
 
+    /// iterator over all solved cells.
     pub fn iter_solved(&self) -> impl Iterator<Item = Cell> + '_ {
         Cell::list().filter(|cell| self.is_digit(cell))
     }
 
+    /// iterator over all unsolved cells.
     pub fn iter_unsolved(&self) -> impl Iterator<Item = Cell> + '_ {
         Cell::list().filter(|cell| self.is_notes(cell))
     }
 
+    /// iterator over all cells solved with `digit`.
     pub fn iter_with_digit(&self, digit: Digit) -> impl Iterator<Item = Cell> + '_ {
         Cell::list().filter(move |cell| self.get_digit(cell) == Some(digit))
     }
 
+    /// iterator over all unsolved cells with `digit` as a note.
     pub fn iter_with_note(&self, digit: Digit) -> impl Iterator<Item = Cell> + '_ {
         Cell::list().filter(move |cell| self.has_note(cell, digit))
     }
 
     // cell sets ---------------------------------------------------------------
 
+    /// set of all unsolved cells.
     pub fn cells_unsolved(&self) -> Set<Cell> {
         self.iter_unsolved().collect()
     }
 
+    /// set of all cells solved with `digit`.
     pub fn cells_with_digit(&self, digit: Digit) -> Set<Cell> {
         self.iter_with_digit(digit).collect()
     }
 
+    /// set of all unsolved cells with `digit` as a note.
     pub fn cells_with_note(&self, digit: Digit) -> Set<Cell> {
         self.iter_with_note(digit).collect()
     }
 }
+
+/// formats the board as a string of 81 characters, read row by row, with `.`
+/// for unsolved cells. this is the format read by
+/// [`Solver::load_board_string`](super::Solver::load_board_string).
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for cell in Cell::list() {
+            match self.get_digit(&cell) {
+                Some(digit) => write!(f, "{digit}")?,
+                None => write!(f, ".")?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use crate::bitset::{Element, Set};
+
+use super::{Board, Cell, Digit};
+
+// =============================================================================
+
+/// finds up to `limit` solutions of `board` by backtracking.
+///
+/// solved cells are kept as they are, and unsolved cells may only take digits
+/// which are still in their notes. if the solved cells already contain a
+/// repeated digit in some unit, there are no solutions.
+pub fn find_solutions(board: &Board, limit: usize) -> Vec<Board> {
+    let mut solutions = Vec::new();
+
+    if let Some(mut search) = Search::new(board) {
+        search.run(limit, &mut |search| solutions.push(search.to_board()));
+    }
+
+    solutions
+}
+
+/// counts the solutions of `board`, stopping once `limit` have been found.
+///
+/// a limit of 2 is enough to tell whether a puzzle has a unique solution.
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    let mut count = 0;
+
+    if let Some(mut search) = Search::new(board) {
+        search.run(limit, &mut |_| count += 1);
+    }
+
+    count
+}
+
+/// returns the solution of `board` if it has exactly one.
+pub fn unique_solution(board: &Board) -> Option<Board> {
+    let [solution] = find_solutions(board, 2).try_into().ok()?;
+
+    Some(solution)
+}
+
+// =============================================================================
+
+struct Search {
+    digits: [Option<Digit>; 81],
+    notes: [Set<Digit>; 81],
+    /// digits placed in each unit, indexed by `Unit::index`
+    placed: [Set<Digit>; 27],
+    found: usize,
+}
+
+impl Search {
+    /// returns `None` if the solved cells of `board` conflict with each other.
+    fn new(board: &Board) -> Option<Self> {
+        let mut search = Search {
+            digits: [None; 81],
+            notes: [Set::new(); 81],
+            placed: [Set::new(); 27],
+            found: 0,
+        };
+
+        for cell in Cell::list() {
+            match board.get_digit(&cell) {
+                Some(digit) => {
+                    if !search.is_allowed(cell, digit) {
+                        return None;
+                    }
+
+                    search.place(cell, digit);
+                }
+                None => search.notes[cell.index()] = *board.get_notes(&cell).unwrap(),
+            }
+        }
+
+        Some(search)
+    }
+
+    fn candidates(&self, cell: Cell) -> Set<Digit> {
+        cell.units()
+            .iter()
+            .fold(self.notes[cell.index()], |notes, unit| {
+                notes - self.placed[unit.index()]
+            })
+    }
+
+    fn is_allowed(&self, cell: Cell, digit: Digit) -> bool {
+        cell.units()
+            .iter()
+            .all(|unit| !self.placed[unit.index()].contains(digit))
+    }
+
+    fn place(&mut self, cell: Cell, digit: Digit) {
+        self.digits[cell.index()] = Some(digit);
+
+        for unit in cell.units() {
+            self.placed[unit.index()].insert(digit);
+        }
+    }
+
+    fn unplace(&mut self, cell: Cell, digit: Digit) {
+        self.digits[cell.index()] = None;
+
+        for unit in cell.units() {
+            self.placed[unit.index()].remove(digit);
+        }
+    }
+
+    /// depth-first search, always branching on the unsolved cell with the
+    /// fewest candidates. calls `on_solution` for each solution found, until
+    /// `limit` solutions have been found.
+    fn run(&mut self, limit: usize, on_solution: &mut dyn FnMut(&Self)) {
+        if self.found >= limit {
+            return;
+        }
+
+        let branch = Cell::list()
+            .filter(|cell| self.digits[cell.index()].is_none())
+            .map(|cell| (cell, self.candidates(cell)))
+            .min_by_key(|(_, candidates)| candidates.len());
+
+        let Some((cell, candidates)) = branch else {
+            self.found += 1;
+            on_solution(self);
+            return;
+        };
+
+        for digit in candidates {
+            self.place(cell, digit);
+            self.run(limit, on_solution);
+            self.unplace(cell, digit);
+
+            if self.found >= limit {
+                return;
+            }
+        }
+    }
+
+    fn to_board(&self) -> Board {
+        let mut board = Board::new();
+
+        for cell in Cell::list() {
+            if let Some(digit) = self.digits[cell.index()] {
+                board.set_digit(cell, digit);
+            }
+        }
+
+        board
+    }
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::Solver;
+
+    fn load(string: &str) -> Board {
+        let mut solver = Solver::new();
+        solver.load_board_string(string);
+        solver.board
+    }
+
+    #[test]
+    fn unique_puzzle() {
+        let board = load(
+            "607005010580007900000060000005000009000936000300000400000080000003600094050200806",
+        );
+
+        assert_eq!(count_solutions(&board, 10), 1);
+
+        let solution = unique_solution(&board).unwrap();
+
+        assert_eq!(
+            solution.to_string(),
+            "697425318584317962132869745215748639748936251369152487976584123823671594451293876"
+        );
+    }
+
+    #[test]
+    fn empty_board_hits_limit() {
+        assert_eq!(count_solutions(&Board::new(), 5), 5);
+        assert_eq!(find_solutions(&Board::new(), 3).len(), 3);
+        assert!(unique_solution(&Board::new()).is_none());
+    }
+
+    #[test]
+    fn conflicting_givens() {
+        let board = load(
+            "660005010580007900000060000005000009000936000300000400000080000003600094050200806",
+        );
+
+        assert_eq!(count_solutions(&board, 10), 0);
+    }
+}
@@ -0,0 +1,285 @@
+use std::{fmt, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::bitset::{Element, Set};
+
+use super::{count_solutions, find_solutions, Block, Board, Cell, Digit, Grade, Solver};
+
+// =============================================================================
+
+/// symmetry of the givens in a generated puzzle
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Symmetry {
+    #[default]
+    None,
+    /// 180 degree rotation about the center
+    Rotational,
+    /// 90 degree rotation about the center
+    Quarter,
+    /// reflection across the vertical center line
+    Horizontal,
+    /// reflection across the horizontal center line
+    Vertical,
+    /// reflection across the main diagonal
+    Diagonal,
+}
+
+impl Symmetry {
+    pub const LIST: [Symmetry; 6] = [
+        Symmetry::None,
+        Symmetry::Rotational,
+        Symmetry::Quarter,
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::Diagonal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Symmetry::None => "none",
+            Symmetry::Rotational => "rotational",
+            Symmetry::Quarter => "quarter",
+            Symmetry::Horizontal => "horizontal",
+            Symmetry::Vertical => "vertical",
+            Symmetry::Diagonal => "diagonal",
+        }
+    }
+
+    /// set of cells which `cell` is mapped to by the symmetry, including itself.
+    pub fn orbit(self, cell: Cell) -> Set<Cell> {
+        let at = |row: usize, col: usize| Cell::from_index(row * 9 + col);
+
+        let r = cell.row().index();
+        let c = cell.col().index();
+
+        let cells = match self {
+            Symmetry::None => vec![cell],
+            Symmetry::Rotational => vec![cell, at(8 - r, 8 - c)],
+            Symmetry::Quarter => vec![cell, at(c, 8 - r), at(8 - r, 8 - c), at(8 - c, r)],
+            Symmetry::Horizontal => vec![cell, at(r, 8 - c)],
+            Symmetry::Vertical => vec![cell, at(8 - r, c)],
+            Symmetry::Diagonal => vec![cell, at(c, r)],
+        };
+
+        cells.into_iter().collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseSymmetryError;
+
+impl FromStr for Symmetry {
+    type Err = ParseSymmetryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symmetry::LIST
+            .into_iter()
+            .find(|symmetry| symmetry.name().eq_ignore_ascii_case(s))
+            .ok_or(ParseSymmetryError)
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// =============================================================================
+
+/// generates random puzzles with a unique solution.
+///
+/// generation is deterministic for a given seed.
+pub struct Generator {
+    rng: Rng,
+    /// symmetry the givens must have
+    pub symmetry: Symmetry,
+    /// grade the puzzle must have, if any
+    pub grade: Option<Grade>,
+    /// number of puzzles to try before giving up on reaching `grade`
+    pub max_attempts: usize,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            rng: Rng::new(seed),
+            symmetry: Symmetry::None,
+            grade: None,
+            max_attempts: 100,
+        }
+    }
+
+    /// generates a puzzle, returning a board with only the givens filled in.
+    ///
+    /// returns `None` if no puzzle with the requested grade was found within
+    /// `max_attempts` tries.
+    pub fn generate(&mut self) -> Option<Board> {
+        for _ in 0..self.max_attempts {
+            let solution = self.filled_grid();
+            let puzzle = self.remove_givens(&solution);
+
+            match self.grade {
+                Some(grade) if rate(&puzzle) != grade => continue,
+                _ => return Some(puzzle),
+            }
+        }
+
+        None
+    }
+
+    /// generates a random, fully solved grid.
+    pub fn filled_grid(&mut self) -> Board {
+        let mut board = Board::new();
+
+        // the diagonal blocks don't share any units, so they can be filled
+        // independently, and the rest of the grid can always be completed
+        for block in [0, 4, 8].map(Block::from_index) {
+            let mut digits = Digit::list().collect_vec();
+            self.rng.shuffle(&mut digits);
+
+            for (cell, digit) in block.cells_iter().zip(digits) {
+                board.set_digit(cell, digit);
+            }
+        }
+
+        find_solutions(&board, 1)
+            .pop()
+            .expect("grid with filled diagonal blocks should be solvable")
+    }
+
+    /// removes givens from `solution` in symmetric groups, in random order,
+    /// keeping each removal only if the puzzle still has a unique solution
+    /// and, if a grade is requested, is not rated above it.
+    fn remove_givens(&mut self, solution: &Board) -> Board {
+        let mut orbits = Vec::new();
+        let mut seen = Set::new();
+
+        for cell in Cell::list() {
+            if seen.contains(cell) {
+                continue;
+            }
+
+            let orbit = self.symmetry.orbit(cell);
+            seen |= orbit;
+            orbits.push(orbit);
+        }
+
+        self.rng.shuffle(&mut orbits);
+
+        let mut puzzle = solution.clone();
+
+        for orbit in orbits {
+            let mut candidate = puzzle.clone();
+
+            for cell in orbit {
+                candidate.clear_cell(cell);
+            }
+
+            if count_solutions(&candidate, 2) != 1 {
+                continue;
+            }
+
+            if matches!(self.grade, Some(grade) if rate(&candidate) > grade) {
+                continue;
+            }
+
+            puzzle = candidate;
+        }
+
+        puzzle
+    }
+}
+
+fn rate(puzzle: &Board) -> Grade {
+    let mut solver = Solver::new();
+
+    solver.load_board(puzzle);
+    solver.solve().rating().grade
+}
+
+// -----------------------------------------------------------------------------
+
+/// xorshift64* pseudorandom number generator
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // splitmix64 step, so that similar seeds give unrelated sequences and
+        // the state is never zero
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        Rng(z.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// random number in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filled_grid_is_valid() {
+        let grid = Generator::new(1).filled_grid();
+
+        assert!(grid.is_solved());
+        assert_eq!(count_solutions(&grid, 2), 1);
+    }
+
+    #[test]
+    fn puzzle_is_unique_and_symmetric() {
+        for symmetry in Symmetry::LIST {
+            let mut generator = Generator::new(7);
+            generator.symmetry = symmetry;
+
+            let puzzle = generator.generate().unwrap();
+
+            assert_eq!(count_solutions(&puzzle, 2), 1);
+
+            for cell in puzzle.iter_solved() {
+                assert!(symmetry.orbit(cell).iter().all(|c| puzzle.is_digit(&c)));
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        let a = Generator::new(42).generate();
+        let b = Generator::new(42).generate();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn target_grade() {
+        let mut generator = Generator::new(3);
+        generator.grade = Some(Grade::Easy);
+
+        let puzzle = generator.generate().unwrap();
+
+        assert_eq!(rate(&puzzle), Grade::Easy);
+    }
+}
@@ -1,11 +1,29 @@
+//! Board representation, solving strategies, the step-by-step solver, a
+//! brute-force solution counter and a puzzle generator.
+
 mod pos;
-pub use pos::{Block, Candidate, Cell, Col, Digit, Line, Row, Unit};
+pub use pos::{Block, Candidate, Cell, Col, Digit, Line, Row, Unit, UnitClass, UnitType};
 
 mod board;
 pub use board::{Board, CellData};
 
 mod strats;
-pub use strats::{Strategy, StrategyResult, STRATEGY_LIST};
+pub use strats::{Profile, Strategy, StrategyBranch, StrategyResult, PROFILE_LIST, STRATEGY_LIST};
+
+mod brute_force;
+pub use brute_force::{count_solutions, find_solutions, unique_solution};
+
+mod verify;
+pub use verify::{verify_result, Violation, ViolationKind};
+
+mod path;
+pub use path::{SolutionPath, SolutionStep};
+
+mod rating;
+pub use rating::{Grade, ParseGradeError, Rating};
+
+mod generator;
+pub use generator::{Generator, ParseSymmetryError, Symmetry};
 
 mod solver;
 pub use solver::{Action as SolverAction, Solver};
@@ -0,0 +1,54 @@
+use super::{Board, Rating, Strategy, StrategyResult};
+
+// =============================================================================
+
+/// a single applied strategy result, along with the board it was found on
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SolutionStep {
+    pub strategy: Strategy,
+    pub result: StrategyResult,
+    /// board before the result was applied
+    pub board: Board,
+}
+
+// =============================================================================
+
+/// ordered list of the steps taken to solve a puzzle
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SolutionPath {
+    pub steps: Vec<SolutionStep>,
+    /// board after the last step was applied
+    pub board: Board,
+}
+
+impl SolutionPath {
+    /// returns `true` if the path ends with a fully solved board.
+    pub fn is_solved(&self) -> bool {
+        self.board.is_solved()
+    }
+
+    /// rates the difficulty of the puzzle based on the steps taken.
+    pub fn rating(&self) -> Rating {
+        Rating::from_path(self)
+    }
+
+    /// iterator over the strategy used in each step.
+    pub fn iter_strategies(&self) -> impl Iterator<Item = Strategy> + '_ {
+        self.steps.iter().map(|step| step.strategy)
+    }
+
+    /// each distinct strategy used, in order of first use, along with the
+    /// number of steps it was used in.
+    pub fn strategy_counts(&self) -> Vec<(Strategy, usize)> {
+        let mut counts: Vec<(Strategy, usize)> = Vec::new();
+
+        for strategy in self.iter_strategies() {
+            match counts.iter_mut().find(|(s, _)| *s == strategy) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((strategy, 1)),
+            }
+        }
+
+        counts
+    }
+}
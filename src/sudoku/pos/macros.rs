@@ -22,22 +22,3 @@ macro_rules! impl_bounded_int_newtype {
 }
 
 pub(super) use impl_bounded_int_newtype;
-
-trait BoundedIntNewtype: Sized {
-    type Repr: Copy + Into<usize> + From<usize>;
-    const BOUND: usize;
-
-    fn new_unchecked(value: Self::Repr) -> Self;
-
-    fn new(value: Self::Repr) -> Option<Self> {
-        (value.into() < Self::BOUND).then_some(Self::new_unchecked(value))
-    }
-
-    fn from_index_unchecked(index: usize) -> Self {
-        Self::new_unchecked(Self::Repr::from(index))
-    }
-
-    fn from_index(index: usize) -> Option<Self> {
-        index.try_into().ok().and_then(Self::new)
-    }
-}
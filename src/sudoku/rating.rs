@@ -0,0 +1,213 @@
+use std::{fmt, str::FromStr};
+
+use super::SolutionPath;
+
+// =============================================================================
+
+/// difficulty classification of a puzzle, from easiest to hardest
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Grade {
+    Easy,
+    Medium,
+    Hard,
+    Unfair,
+    Extreme,
+}
+
+impl Grade {
+    /// list of all grades, from easiest to hardest.
+    pub const LIST: [Grade; 5] = [
+        Grade::Easy,
+        Grade::Medium,
+        Grade::Hard,
+        Grade::Unfair,
+        Grade::Extreme,
+    ];
+
+    /// grade of a puzzle whose hardest step has the given difficulty.
+    pub fn from_max_difficulty(difficulty: u32) -> Self {
+        match difficulty {
+            0..=20 => Grade::Easy,
+            21..=100 => Grade::Medium,
+            101..=200 => Grade::Hard,
+            201..=300 => Grade::Unfair,
+            _ => Grade::Extreme,
+        }
+    }
+
+    /// grade of a puzzle whose steps have the given total difficulty.
+    pub fn from_total_difficulty(total: u32) -> Self {
+        match total {
+            0..=800 => Grade::Easy,
+            801..=1000 => Grade::Medium,
+            1001..=1600 => Grade::Hard,
+            1601..=1800 => Grade::Unfair,
+            _ => Grade::Extreme,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Grade::Easy => "Easy",
+            Grade::Medium => "Medium",
+            Grade::Hard => "Hard",
+            Grade::Unfair => "Unfair",
+            Grade::Extreme => "Extreme",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseGradeError;
+
+impl FromStr for Grade {
+    type Err = ParseGradeError;
+
+    /// parses a grade from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grade::LIST
+            .into_iter()
+            .find(|grade| grade.name().eq_ignore_ascii_case(s))
+            .ok_or(ParseGradeError)
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// =============================================================================
+
+/// difficulty rating of a puzzle, computed from the path taken to solve it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rating {
+    /// difficulty of the hardest step
+    pub max_difficulty: u32,
+    /// sum of the difficulties of every step
+    pub total_difficulty: u32,
+    /// the harder of the grades given by `max_difficulty` and
+    /// `total_difficulty`, or `Extreme` if the path does not solve the puzzle
+    pub grade: Grade,
+}
+
+impl Rating {
+    pub fn from_path(path: &SolutionPath) -> Self {
+        let max_difficulty = path
+            .iter_strategies()
+            .map(|strategy| strategy.difficulty)
+            .max()
+            .unwrap_or(0);
+
+        let total_difficulty = path
+            .iter_strategies()
+            .map(|strategy| strategy.difficulty)
+            .sum();
+
+        // finishing the puzzle needs something beyond the available strategies
+        let grade = if path.is_solved() {
+            Grade::from_max_difficulty(max_difficulty)
+                .max(Grade::from_total_difficulty(total_difficulty))
+        } else {
+            Grade::Extreme
+        };
+
+        Rating {
+            max_difficulty,
+            total_difficulty,
+            grade,
+        }
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}/{})",
+            self.grade, self.max_difficulty, self.total_difficulty
+        )
+    }
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::{Profile, Solver};
+
+    fn rate(puzzle: &str) -> Rating {
+        let mut solver = Solver::new();
+        solver.load_board_string(puzzle);
+        solver.solve().rating()
+    }
+
+    #[test]
+    fn singles_only_is_easy() {
+        let rating = rate(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        );
+
+        assert!(rating.max_difficulty <= 14);
+        assert_eq!(rating.grade, Grade::Easy);
+    }
+
+    #[test]
+    fn many_chains_are_extreme() {
+        let mut solver = Solver::new();
+        solver.load_board_string(
+            "607005010580007900000060000005000009000936000300000400000080000003600094050200806",
+        );
+
+        let path = solver.solve();
+        let rating = path.rating();
+
+        let hardest = path
+            .iter_strategies()
+            .map(|strategy| strategy.difficulty)
+            .max()
+            .unwrap();
+
+        // the hardest step alone only makes the puzzle unfair, but there are
+        // enough chains to make the total extreme
+        assert_eq!(rating.max_difficulty, hardest);
+        assert_eq!(Grade::from_max_difficulty(hardest), Grade::Unfair);
+        assert_eq!(
+            Grade::from_total_difficulty(rating.total_difficulty),
+            Grade::Extreme
+        );
+        assert_eq!(rating.grade, Grade::Extreme);
+    }
+
+    #[test]
+    fn unsolved_is_extreme() {
+        let singles = Profile::from_name("singles-only").unwrap();
+
+        let mut solver = Solver::from_profile(&singles);
+        solver.load_board_string(
+            "607005010580007900000060000005000009000936000300000400000080000003600094050200806",
+        );
+
+        let path = solver.solve();
+
+        assert!(!path.is_solved());
+        assert_eq!(path.rating().grade, Grade::Extreme);
+    }
+
+    #[test]
+    fn parse_grade() {
+        for grade in Grade::LIST {
+            assert_eq!(grade.to_string().parse::<Grade>().unwrap(), grade);
+        }
+
+        assert_eq!("hard".parse::<Grade>().unwrap(), Grade::Hard);
+        assert!("impossible".parse::<Grade>().is_err());
+    }
+
+    #[test]
+    fn grades_are_ordered() {
+        assert!(Grade::LIST.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -1,121 +1,351 @@
-use log::{debug, info};
+use std::collections::HashSet;
+
+use log::{error, info, warn};
 
 use crate::bitset::{Element, Set};
 
-use super::{Board, Cell, Digit, Strategy, StrategyResult, STRATEGY_LIST};
+use super::{
+    strats::find_by_trial, unique_solution, verify_result, Board, Cell, Digit, Profile,
+    SolutionPath, SolutionStep, Strategy, StrategyResult, Violation, STRATEGY_LIST,
+};
 
 // =============================================================================
 
+/// actions which can be dispatched to a [`Solver`] through [`Solver::take_action`]
 pub enum Action {
     Reset,
     LoadBoardString(String),
     Undo,
     Step,
+    Solve,
     SetFocus(Option<Digit>),
+    LoadProfile(Profile),
+    SetStrategyEnabled(Strategy, bool),
+    MoveStrategy(usize, usize),
+    SetAssumeUnique(bool),
+    SetAllowTrial(bool),
 }
 
 // =============================================================================
 
+/// steps through a puzzle by applying strategies to a board one at a time.
+///
+/// each step alternates between finding the next [`StrategyResult`] and
+/// applying it, so the pending result can be displayed before it is applied.
+/// applied results are kept as a [`SolutionPath`], which can be undone one
+/// step at a time.
+///
+/// strategies are tried in order, skipping any which have been disabled. the
+/// order and set of enabled strategies can be configured directly or loaded
+/// from a [`Profile`].
+///
+/// strategies which assume the puzzle has a unique solution, such as unique
+/// rectangles, are skipped unless enabled with [`Solver::set_assume_unique`].
+///
+/// trial and error is only used once allowed with [`Solver::set_allow_trial`],
+/// after every other strategy, using the enabled strategies to look for
+/// contradictions.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Solver {
     // private
     strategies: Vec<Strategy>,
-    history: Vec<Board>,
+    disabled: HashSet<&'static str>,
+    history: Vec<SolutionStep>,
+    verify: bool,
+    assume_unique: bool,
+    allow_trial: bool,
+    solution: Option<Board>,
     // public
     pub given: Set<Cell>,
     pub board: Board,
     pub result: Option<StrategyResult>,
+    pub strategy: Option<Strategy>,
     pub focus_digit: Option<Digit>,
+    pub violations: Vec<Violation>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
 }
 
 impl Solver {
     // constructors ------------------------------------------------------------
 
+    /// creates a solver with an empty board and the default strategy list.
     pub fn new() -> Self {
         Solver {
             strategies: STRATEGY_LIST.to_vec(),
+            disabled: HashSet::new(),
             history: Vec::new(),
+            verify: false,
+            assume_unique: false,
+            allow_trial: false,
+            solution: None,
             given: Set::new(),
             board: Board::new(),
             result: None,
+            strategy: None,
             focus_digit: None,
+            violations: Vec::new(),
         }
     }
 
+    /// creates a solver with only the strategies in `profile` enabled.
+    pub fn from_profile(profile: &Profile) -> Self {
+        let mut solver = Solver::new();
+        solver.load_profile(profile);
+        solver
+    }
+
     // actions -----------------------------------------------------------------
 
+    /// performs `action`, returning the updated solver.
     pub fn take_action(mut self, action: Action) -> Self {
         match action {
             Action::Reset => self.reset(),
             Action::LoadBoardString(string) => self.load_board_string(&string),
             Action::Undo => self.undo(),
             Action::Step => self.step(),
+            Action::Solve => {
+                self.solve();
+            }
             Action::SetFocus(digit) => self.set_focus(digit),
+            Action::LoadProfile(profile) => self.load_profile(&profile),
+            Action::SetStrategyEnabled(strategy, enabled) => self.set_enabled(&strategy, enabled),
+            Action::MoveStrategy(from, to) => self.move_strategy(from, to),
+            Action::SetAssumeUnique(assume_unique) => self.set_assume_unique(assume_unique),
+            Action::SetAllowTrial(allow_trial) => self.set_allow_trial(allow_trial),
         }
 
         self
     }
 
-    fn reset(&mut self) {
+    /// clears the board, history and any pending result.
+    pub fn reset(&mut self) {
         self.history.clear();
+        self.given.clear();
         self.board.clear();
         self.result = None;
+        self.strategy = None;
         self.focus_digit = None;
+        self.solution = None;
+        self.violations.clear();
     }
 
-    fn load_board_string(&mut self, string: &str) {
-        self.reset();
+    /// resets the solver and loads a puzzle from a string of 81 characters,
+    /// read row by row. digits `1`-`9` are givens; any other character is an
+    /// empty cell.
+    pub fn load_board_string(&mut self, string: &str) {
+        let mut board = Board::new();
 
         for cell in Cell::list() {
             let i = cell.index();
             let digit = string.get(i..(i + 1)).and_then(|s| s.parse().ok());
 
             if let Some(digit) = digit {
-                self.given.insert(cell);
-                self.board.set_digit(cell, digit);
+                board.set_digit(cell, digit);
             }
         }
+
+        self.load_board(&board);
     }
 
-    fn undo(&mut self) {
-        if let Some(board) = self.history.pop() {
-            self.board = board;
+    /// resets the solver and loads a puzzle from a board, taking its solved
+    /// cells as givens.
+    pub fn load_board(&mut self, board: &Board) {
+        self.reset();
+
+        self.given = board.iter_solved().collect();
+        self.board = board.clone();
+
+        if self.verify {
+            self.compute_solution();
         }
     }
 
-    fn step(&mut self) {
-        // let start = Date::now();
+    /// restores the board as it was before the last applied result, dropping
+    /// any pending result found on the newer board.
+    pub fn undo(&mut self) {
+        if let Some(step) = self.history.pop() {
+            self.board = step.board;
+            self.result = None;
+            self.strategy = None;
+        }
+    }
 
+    /// applies the pending result if there is one, otherwise searches for the
+    /// next result with the first strategy that finds one.
+    pub fn step(&mut self) {
         match self.result {
             Some(_) => self.apply_current_result(),
             None => self.find_next_strategy(),
         }
+    }
+
+    /// steps until the board is solved or no strategy finds anything, applying
+    /// the pending result first if there is one.
+    ///
+    /// returns the path taken since the puzzle was loaded, including any steps
+    /// taken before calling this.
+    pub fn solve(&mut self) -> SolutionPath {
+        loop {
+            if self.result.is_none() {
+                if self.board.is_solved() {
+                    break;
+                }
+
+                self.find_next_strategy();
+
+                if self.result.is_none() {
+                    break;
+                }
+            }
+
+            self.apply_current_result();
+        }
 
-        // let end = Date::now();
-        // let elapsed_time = end - start;
-        // info!("step took: {:?}", elapsed_time);
+        self.solution_path()
     }
 
-    fn set_focus(&mut self, digit: Option<Digit>) {
+    /// returns the steps applied since the puzzle was loaded.
+    pub fn solution_path(&self) -> SolutionPath {
+        SolutionPath {
+            steps: self.history.clone(),
+            board: self.board.clone(),
+        }
+    }
+
+    /// toggles the digit highlighted in the frontend.
+    pub fn set_focus(&mut self, digit: Option<Digit>) {
         self.focus_digit = (digit != self.focus_digit).then_some(digit).flatten();
     }
 
-    // mutating action helpers -------------------------------------------------
+    /// enables or disables verification mode.
+    ///
+    /// when enabled, the unique solution of the puzzle is computed when it is
+    /// loaded, and every result found is checked against it. any deduction
+    /// which contradicts the solution is logged and recorded in `violations`.
+    /// puzzles without a unique solution cannot be verified.
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+
+        if verify {
+            self.compute_solution();
+        } else {
+            self.solution = None;
+        }
+    }
+
+    /// allows or forbids strategies which are only sound if the puzzle has a
+    /// unique solution.
+    pub fn set_assume_unique(&mut self, assume_unique: bool) {
+        self.assume_unique = assume_unique;
+    }
+
+    /// returns `true` if strategies relying on a unique solution are used.
+    pub fn assumes_unique(&self) -> bool {
+        self.assume_unique
+    }
+
+    /// allows or forbids trial and error, which is off by default so puzzles
+    /// are solved with logic alone.
+    pub fn set_allow_trial(&mut self, allow_trial: bool) {
+        self.allow_trial = allow_trial;
+    }
 
-    fn remember_board(&mut self) {
-        self.history.push(self.board.clone());
+    /// returns `true` if trial and error strategies are used.
+    pub fn allows_trial(&self) -> bool {
+        self.allow_trial
     }
 
+    // strategy configuration --------------------------------------------------
+
+    /// all strategies, enabled or not, in the order they are tried.
+    pub fn strategies(&self) -> &[Strategy] {
+        &self.strategies
+    }
+
+    /// enabled strategies, in the order they are tried.
+    pub fn enabled_strategies(&self) -> impl Iterator<Item = &Strategy> + '_ {
+        self.strategies
+            .iter()
+            .filter(|strategy| self.is_enabled(strategy))
+    }
+
+    /// returns `true` if `strategy` is in the list and has not been disabled.
+    pub fn is_enabled(&self, strategy: &Strategy) -> bool {
+        self.strategies.contains(strategy) && !self.disabled.contains(strategy.name)
+    }
+
+    /// enables or disables `strategy`, keeping its place in the order.
+    pub fn set_enabled(&mut self, strategy: &Strategy, enabled: bool) {
+        if enabled {
+            self.disabled.remove(strategy.name);
+        } else {
+            self.disabled.insert(strategy.name);
+        }
+    }
+
+    /// replaces the strategy list, with every strategy enabled.
+    pub fn set_strategies(&mut self, strategies: &[Strategy]) {
+        self.strategies = strategies.to_vec();
+        self.disabled.clear();
+    }
+
+    /// moves the strategy at index `from` to index `to`, shifting the ones in
+    /// between. does nothing if either index is out of range.
+    pub fn move_strategy(&mut self, from: usize, to: usize) {
+        if from >= self.strategies.len() || to >= self.strategies.len() {
+            return;
+        }
+
+        let strategy = self.strategies.remove(from);
+        self.strategies.insert(to, strategy);
+    }
+
+    /// enables exactly the strategies in `profile`, keeping the current order.
+    pub fn load_profile(&mut self, profile: &Profile) {
+        self.disabled = self
+            .strategies
+            .iter()
+            .filter(|strategy| !profile.contains(strategy))
+            .map(|strategy| strategy.name)
+            .collect();
+    }
+
+    // mutating action helpers -------------------------------------------------
+
     fn find_next_strategy(&mut self) {
-        for strategy in &self.strategies {
+        let strategies: Vec<Strategy> = self
+            .enabled_strategies()
+            .filter(|strategy| self.assume_unique || !strategy.assumes_uniqueness())
+            .filter(|strategy| self.allow_trial || !strategy.is_trial_and_error())
+            .copied()
+            .collect();
+
+        for strategy in &strategies {
             // debug!("trying strategy: {}", strategy.name);
 
-            let result = (strategy.find)(&self.board);
+            let result = if strategy.is_trial_and_error() {
+                find_by_trial(&self.board, &strategies)
+            } else {
+                (strategy.find)(&self.board)
+            };
 
             if result.is_nontrivial() {
                 info!("Found strategy: {}", strategy.name);
 
+                if let Some(solution) = &self.solution {
+                    for violation in verify_result(solution, strategy, &result) {
+                        error!("Unsound deduction: {violation}");
+                        self.violations.push(violation);
+                    }
+                }
+
                 self.result = Some(result);
+                self.strategy = Some(*strategy);
                 return;
             }
         }
@@ -123,16 +353,24 @@ impl Solver {
         info!("no strategy found");
     }
 
+    fn compute_solution(&mut self) {
+        self.solution = unique_solution(&self.board);
+
+        if self.solution.is_none() {
+            warn!("Puzzle does not have a unique solution, cannot verify strategies");
+        }
+    }
+
     fn apply_current_result(&mut self) {
         // .take() takes ownership of the result, leaving self.result as None.
-        // This is necessary because we need to borrow self.result mutably in
-        // order to call self.remember_board().
-        let Some(result) = self.result.take() else {
-            log::error!("Solver::apply_current_result() called with no result");
+        // This is necessary because the result is moved into the history once
+        // it has been applied.
+        let (Some(result), Some(strategy)) = (self.result.take(), self.strategy.take()) else {
+            error!("Solver::apply_current_result() called with no result");
             return;
         };
 
-        self.remember_board();
+        let board = self.board.clone();
 
         for solution in result.solutions {
             self.board.input_solution(solution);
@@ -141,5 +379,217 @@ impl Solver {
         for elimination in result.eliminations {
             self.board.input_elimination(elimination);
         }
+
+        self.history.push(SolutionStep {
+            strategy,
+            result,
+            board,
+        });
+    }
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    const PUZZLE: &str =
+        "607005010580007900000060000005000009000936000300000400000080000003600094050200806";
+
+    #[test]
+    fn step_through_puzzle() {
+        const SINGLES_PUZZLE: &str =
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+        let mut solver = Solver::new();
+
+        solver.load_board_string(SINGLES_PUZZLE);
+
+        assert_eq!(solver.given.len(), 32);
+
+        // each cell takes at most one step to find and one to apply
+        for _ in 0..2 * 81 {
+            if solver.board.is_solved() {
+                break;
+            }
+
+            solver.step();
+        }
+
+        assert!(solver.board.is_solved());
+    }
+
+    #[test]
+    fn solve_and_undo() {
+        let mut solver = Solver::new();
+
+        solver.load_board_string(PUZZLE);
+
+        let start = solver.board.clone();
+
+        solver.step();
+        solver.step();
+
+        let path = solver.solve();
+
+        assert!(path.is_solved());
+        assert_eq!(path.board, solver.board);
+        assert_eq!(path.steps[0].board, start);
+
+        for (step, next) in path.steps.iter().tuple_windows() {
+            assert_ne!(step.board, next.board);
+        }
+
+        for _ in 0..path.steps.len() {
+            solver.undo();
+        }
+
+        assert_eq!(solver.board, start);
+        assert!(solver.solution_path().steps.is_empty());
+    }
+
+    #[test]
+    fn undo_drops_pending_result() {
+        let mut solver = Solver::new();
+
+        solver.load_board_string(PUZZLE);
+
+        // find and apply a result, then find the next one
+        solver.step();
+        let first = solver.result.clone();
+        solver.step();
+        let applied = solver.board.clone();
+        solver.step();
+        assert!(solver.result.is_some());
+
+        solver.undo();
+
+        assert!(solver.result.is_none());
+        assert!(solver.strategy.is_none());
+
+        // stepping again finds the first result on the restored board
+        solver.step();
+        assert_eq!(solver.result, first);
+        solver.step();
+        assert_eq!(solver.board, applied);
+    }
+
+    #[test]
+    fn singles_only_profile() {
+        let profile = Profile::from_name("singles-only").unwrap();
+        let mut solver = Solver::from_profile(&profile);
+
+        solver.load_board_string(PUZZLE);
+
+        let path = solver.solve();
+
+        assert!(!path.is_solved());
+        assert!(path
+            .iter_strategies()
+            .all(|strategy| profile.contains(&strategy)));
+    }
+
+    #[test]
+    fn uniqueness_strategies_need_assumption() {
+        const RECTANGLE_PUZZLE: &str =
+            "...7..28..3..1............97..59..6..1..2.4.58.........5.9.7..2....8....69...5..1";
+
+        let mut solver = Solver::new();
+
+        solver.load_board_string(RECTANGLE_PUZZLE);
+
+        assert!(!solver
+            .solve()
+            .iter_strategies()
+            .any(|strategy| strategy.assumes_uniqueness()));
+
+        solver.set_assume_unique(true);
+        solver.load_board_string(RECTANGLE_PUZZLE);
+
+        let path = solver.solve();
+
+        assert!(path.is_solved());
+        assert!(path
+            .iter_strategies()
+            .any(|strategy| strategy.assumes_uniqueness()));
+    }
+
+    #[test]
+    fn trial_is_opt_in() {
+        let singles = Profile::from_name("singles-only").unwrap().strategies;
+        let trial = *STRATEGY_LIST
+            .iter()
+            .find(|s| s.is_trial_and_error())
+            .unwrap();
+
+        let mut solver = Solver::new();
+
+        solver.set_strategies(&[singles, &[trial]].concat());
+        solver.load_board_string(PUZZLE);
+
+        assert!(!solver.allows_trial());
+
+        let path = solver.solve();
+
+        assert!(!path.is_solved());
+        assert!(!path.iter_strategies().any(|strategy| strategy == trial));
+
+        solver.set_allow_trial(true);
+        solver.load_board_string(PUZZLE);
+
+        let path = solver.solve();
+
+        assert!(path.is_solved());
+        assert!(path.iter_strategies().any(|strategy| strategy == trial));
+    }
+
+    #[test]
+    fn trial_is_skipped_unless_allowed() {
+        let singles = Profile::from_name("singles-only").unwrap().strategies;
+        let trial = *STRATEGY_LIST
+            .iter()
+            .find(|s| s.is_trial_and_error())
+            .unwrap();
+
+        let mut solver = Solver::new();
+
+        // trial and error comes first, so it is picked as soon as it's allowed
+        solver.set_strategies(&[&[trial], singles].concat());
+        solver.load_board_string(PUZZLE);
+        solver.step();
+
+        assert!(solver.strategy.is_some_and(|strategy| strategy != trial));
+
+        solver.set_allow_trial(true);
+        solver.load_board_string(PUZZLE);
+        solver.step();
+
+        assert_eq!(solver.strategy, Some(trial));
+    }
+
+    #[test]
+    fn configure_strategies() {
+        let aic = *STRATEGY_LIST.iter().find(|s| s.name == "AIC").unwrap();
+        let last = STRATEGY_LIST.len() - 1;
+
+        let mut solver = Solver::new();
+
+        solver.set_enabled(&aic, false);
+
+        assert!(!solver.is_enabled(&aic));
+        assert_eq!(solver.enabled_strategies().count(), last);
+
+        solver.set_enabled(&aic, true);
+        solver.move_strategy(
+            solver.strategies().iter().position(|s| *s == aic).unwrap(),
+            0,
+        );
+
+        assert!(solver.is_enabled(&aic));
+        assert_eq!(solver.strategies()[0], aic);
+        assert_eq!(solver.strategies().len(), STRATEGY_LIST.len());
     }
 }
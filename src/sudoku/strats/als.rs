@@ -0,0 +1,343 @@
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Board, Candidate, Cell, Digit, Unit},
+};
+
+use super::{Strategy, StrategyResult};
+
+// =============================================================================
+
+pub const ALS_XZ: Strategy = Strategy {
+    name: "ALS-XZ",
+    difficulty: 300,
+    find: find_als_xz,
+};
+
+pub const ALS_XY_WING: Strategy = Strategy {
+    name: "ALS-XY-Wing",
+    difficulty: 320,
+    find: find_als_xy_wing,
+};
+
+pub const DEATH_BLOSSOM: Strategy = Strategy {
+    name: "Death Blossom",
+    difficulty: 360,
+    find: find_death_blossom,
+};
+
+// =============================================================================
+
+/// an almost locked set: `n` unsolved cells within a unit with `n + 1` digits
+/// between them. if any one of the digits is removed, the rest are locked into
+/// the cells.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Als {
+    pub cells: Set<Cell>,
+    pub digits: Set<Digit>,
+}
+
+impl Als {
+    /// every almost locked set on the board, in board order. sets lying in
+    /// more than one unit are only listed once.
+    pub fn find_all(board: &Board) -> Vec<Als> {
+        let mut list = Vec::new();
+
+        for unit in Unit::iter_all() {
+            let cells = unit.cells_set() & board.cells_unsolved();
+
+            for size in 1..cells.len() {
+                for als_cells in cells.iter().combinations(size) {
+                    let digits = als_cells
+                        .iter()
+                        .map(|cell| *board.get_notes(cell).unwrap())
+                        .fold(Set::new(), |acc, notes| acc | notes);
+
+                    if digits.len() != size + 1 {
+                        continue;
+                    }
+
+                    let als = Als {
+                        cells: als_cells.into_iter().collect(),
+                        digits,
+                    };
+
+                    if !list.contains(&als) {
+                        list.push(als);
+                    }
+                }
+            }
+        }
+
+        list
+    }
+
+    /// cells of the set with `digit` as a note.
+    pub fn cells_with(&self, board: &Board, digit: Digit) -> Set<Cell> {
+        self.cells & board.cells_with_note(digit)
+    }
+
+    /// every candidate in the set.
+    pub fn candidates(&self, board: &Board) -> Set<Candidate> {
+        self.cells
+            .iter()
+            .map(|cell| board.get_notes(&cell).unwrap().map(|d| (cell, d).into()))
+            .sum()
+    }
+
+    /// digits common to both sets which can be in at most one of them, since
+    /// every cell with the digit in one sees every cell with it in the other.
+    fn restricted_commons(&self, other: &Als, board: &Board) -> Set<Digit> {
+        (self.digits & other.digits)
+            .iter()
+            .filter(|&digit| {
+                let cells = self.cells_with(board, digit);
+                let other_cells = other.cells_with(board, digit);
+
+                (cells & other_cells).is_empty()
+                    && cells
+                        .iter()
+                        .all(|cell| other_cells.is_subset(&cell.neighbors()))
+            })
+            .collect()
+    }
+}
+
+/// candidates of `digit` in cells which see every cell in `cells` with it.
+fn seen_by_all(board: &Board, cells: Set<Cell>, digit: Digit) -> Set<Candidate> {
+    (cells & board.cells_with_note(digit))
+        .iter()
+        .fold(board.cells_with_note(digit) - cells, |acc, cell| {
+            acc & cell.neighbors()
+        })
+        .map(|cell| (cell, digit).into())
+}
+
+// -----------------------------------------------------------------------------
+
+/// finds two disjoint sets with a restricted common digit `x`. at most one of
+/// them can have `x`, so the other is locked, and any other common digit `z`
+/// must be in one of them. so `z` can be eliminated from cells which see every
+/// cell with `z` in both sets.
+fn find_als_xz(board: &Board) -> StrategyResult {
+    let list = Als::find_all(board);
+
+    for (a, b) in list.iter().tuple_combinations() {
+        if (a.cells & b.cells).is_nonempty() {
+            continue;
+        }
+
+        let restricted = a.restricted_commons(b, board);
+
+        if restricted.is_empty() {
+            continue;
+        }
+
+        let eliminations: Set<Candidate> = restricted
+            .iter()
+            .flat_map(|x| (a.digits & b.digits) - Set::singleton(x))
+            .unique()
+            .map(|z| seen_by_all(board, a.cells | b.cells, z))
+            .sum();
+
+        if eliminations.is_empty() {
+            continue;
+        }
+
+        return StrategyResult {
+            eliminations,
+            highlights: a.candidates(board),
+            highlights2: b.candidates(board),
+            ..Default::default()
+        };
+    }
+
+    StrategyResult::default()
+}
+
+/// finds two sets `a` and `b` with restricted common digits `x` and `y`
+/// respectively with a third set `c`, all disjoint. if neither `a` nor `b` had
+/// a common digit `z`, both would be locked, taking `x` and `y` from `c`, which
+/// would then have too few digits. so `z` can be eliminated from cells which
+/// see every cell with `z` in `a` and `b`.
+fn find_als_xy_wing(board: &Board) -> StrategyResult {
+    let list = Als::find_all(board);
+
+    for c in &list {
+        let wings = list
+            .iter()
+            .filter(|als| (als.cells & c.cells).is_empty())
+            .map(|als| (als, als.restricted_commons(c, board)))
+            .filter(|(_, restricted)| restricted.is_nonempty())
+            .collect_vec();
+
+        for ((a, a_restricted), (b, b_restricted)) in wings.iter().tuple_combinations() {
+            if (a.cells & b.cells).is_nonempty() {
+                continue;
+            }
+
+            let eliminations: Set<Candidate> = a_restricted
+                .iter()
+                .cartesian_product(b_restricted.iter())
+                .filter(|(x, y)| x != y)
+                .flat_map(|(x, y)| (a.digits & b.digits) - Set::from([x, y].as_slice()))
+                .unique()
+                .map(|z| seen_by_all(board, a.cells | b.cells, z))
+                .sum();
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            return StrategyResult {
+                eliminations,
+                highlights: a.candidates(board) | b.candidates(board),
+                highlights2: c.candidates(board),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// finds a stem cell and, for each of its notes, a petal set not containing
+/// the stem, whose cells with that digit all see the stem. whichever digit the
+/// stem is, its petal loses that digit and is locked. so a digit `z` in every
+/// petal but not the stem can be eliminated from cells which see every cell
+/// with `z` in the petals.
+fn find_death_blossom(board: &Board) -> StrategyResult {
+    let list = Als::find_all(board);
+
+    for stem in board.iter_unsolved() {
+        let stem_notes = *board.get_notes(&stem).unwrap();
+
+        let petals = stem_notes
+            .iter()
+            .map(|digit| {
+                list.iter()
+                    .filter(|als| {
+                        !als.cells.contains(stem)
+                            && als.digits.contains(digit)
+                            && als.cells_with(board, digit).is_subset(&stem.neighbors())
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        for z in Set::<Digit>::full() - stem_notes {
+            let z_petals = petals
+                .iter()
+                .map(|list| {
+                    list.iter()
+                        .copied()
+                        .filter(|als| als.digits.contains(z))
+                        .collect_vec()
+                })
+                .collect_vec();
+
+            let mut chosen = Vec::new();
+
+            if !choose_petals(board, z, &z_petals, &mut chosen, board.cells_with_note(z)) {
+                continue;
+            }
+
+            let petal_cells = chosen.iter().map(|als| als.cells).sum();
+
+            return StrategyResult {
+                eliminations: seen_by_all(board, petal_cells, z),
+                highlights: chosen.iter().map(|als| als.candidates(board)).sum(),
+                highlights2: stem_notes.map(|digit| (stem, digit).into()),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// picks one set from each of `petals` in turn so that some cell outside them
+/// with `z` sees every cell with `z` in them. `targets` holds the cells still
+/// seeing every chosen petal, and the search backtracks once it is empty.
+fn choose_petals<'a>(
+    board: &Board,
+    z: Digit,
+    petals: &[Vec<&'a Als>],
+    chosen: &mut Vec<&'a Als>,
+    targets: Set<Cell>,
+) -> bool {
+    let Some((options, rest)) = petals.split_first() else {
+        return true;
+    };
+
+    for &als in options {
+        let targets = als
+            .cells_with(board, z)
+            .iter()
+            .fold(targets - als.cells, |acc, cell| acc & cell.neighbors());
+
+        if targets.is_empty() {
+            continue;
+        }
+
+        chosen.push(als);
+
+        if choose_petals(board, z, rest, chosen, targets) {
+            return true;
+        }
+
+        chosen.pop();
+    }
+
+    false
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, board_before,
+    };
+
+    #[test]
+    fn als_xz() {
+        let board = board_before(
+            "8......6..61....4..9.....25..7.6...23..5......85..74...4..2..57.5..8...6...9.....",
+            ALS_XZ,
+        );
+
+        assert_eliminations(ALS_XZ, &board, "C4#3");
+    }
+
+    #[test]
+    fn als_xy_wing() {
+        let board = board_before(
+            "....8.79..2..4....4..6..1.....714..8.6....9..3.4..9.1.2..........5....6...83.14..",
+            ALS_XY_WING,
+        );
+
+        assert_eliminations(ALS_XY_WING, &board, "I5#9");
+    }
+
+    #[test]
+    fn death_blossom() {
+        let board = board_before(
+            "..983..2..2...7.......6.3..7.3...6..64....5...8.....9.........95..4...7..7..528..",
+            DEATH_BLOSSOM,
+        );
+
+        assert_eliminations(DEATH_BLOSSOM, &board, "I8#4");
+    }
+
+    #[test]
+    fn no_als() {
+        let board = basic_board(
+            "81.3.....7....4....96...3.......5496..1.6..2....4......4.9.6..........52....1.67.",
+        );
+
+        assert_finds_nothing(&board, &[ALS_XZ, ALS_XY_WING, DEATH_BLOSSOM]);
+    }
+}
@@ -1,6 +1,8 @@
+use itertools::Itertools;
+
 use crate::{
     bitset::Set,
-    sudoku::{pos::UnitClass, Cell},
+    sudoku::{pos::UnitClass, Board, Candidate, Cell, Digit, Unit},
     util::TryIntoArray,
 };
 
@@ -10,36 +12,299 @@ use super::{Strategy, StrategyResult};
 
 pub const BUG_PLUS_1: Strategy = Strategy {
     name: "Bug+1",
+    difficulty: 100,
     find: |board| {
-        let non_bivalue_cells: Set<Cell> = board
-            .iter_unsolved()
-            .filter(|cell| board.count_notes(cell) != 2)
-            .collect();
-
-        let Ok(bug_cell) = non_bivalue_cells.try_singleton() else {
+        let Some(extras) = find_bug_extras(board) else {
             return StrategyResult::default();
         };
 
-        let bug_notes = board.get_notes(&bug_cell).unwrap();
-
-        if bug_notes.len() != 3 {
+        let Ok(extra) = extras.try_singleton() else {
             return StrategyResult::default();
+        };
+
+        StrategyResult {
+            solutions: Set::singleton(extra),
+            ..Default::default()
         }
+    },
+};
 
-        let bug_digit = bug_notes.iter().find(|&digit| {
-            bug_cell
-                .units()
-                .iter()
-                .all(|unit| (unit.cells_set() & board.cells_with_note(digit)).len() == 3)
-        });
+pub const BUG_TYPE_2: Strategy = Strategy {
+    name: "Bug Type 2",
+    difficulty: 100,
+    find: |board| {
+        let Some(extras) = find_bug_extras(board) else {
+            return StrategyResult::default();
+        };
 
-        let Some(bug_digit) = bug_digit else {
+        let Ok(digit) = extras.map(|candidate| candidate.digit()).try_singleton() else {
             return StrategyResult::default();
         };
 
+        let elim_cells = extras
+            .iter()
+            .fold(board.cells_with_note(digit), |acc, extra| {
+                acc & extra.cell().neighbors()
+            });
+
         StrategyResult {
-            solutions: Set::singleton((bug_cell, bug_digit).into()),
+            eliminations: elim_cells.map(|cell| (cell, digit).into()),
+            highlights: extras,
             ..Default::default()
         }
     },
 };
+
+pub const BUG_TYPE_3: Strategy = Strategy {
+    name: "Bug Type 3",
+    difficulty: 120,
+    find: find_bug_type_3,
+};
+
+pub const BUG_TYPE_4: Strategy = Strategy {
+    name: "Bug Type 4",
+    difficulty: 110,
+    find: find_bug_type_4,
+};
+
+// =============================================================================
+
+/// finds the extra candidates which, if removed, would leave a bivalue
+/// universal grave: every unsolved cell with two notes, and every note
+/// appearing exactly twice in each unit. such a board would have either no
+/// solution or several, so in a puzzle with a unique solution at least one of
+/// the extra candidates must be true.
+///
+/// a candidate in a cell with more than two notes is counted as extra if its
+/// digit appears more than twice in each of the cell's units. returns `None`
+/// if there are no extra candidates, or removing them doesn't leave a bug.
+fn find_bug_extras(board: &Board) -> Option<Set<Candidate>> {
+    let count = |unit: Unit, digit: Digit| (unit.cells_set() & board.cells_with_note(digit)).len();
+
+    let extras: Set<Candidate> = board
+        .iter_unsolved()
+        .filter(|cell| board.count_notes(cell) > 2)
+        .flat_map(|cell| {
+            board
+                .get_notes(&cell)
+                .unwrap()
+                .iter()
+                .filter(move |&digit| cell.units().iter().all(|&unit| count(unit, digit) > 2))
+                .map(move |digit| Candidate::from((cell, digit)))
+        })
+        .collect();
+
+    if extras.is_empty() {
+        return None;
+    }
+
+    let all_bivalue = board.iter_unsolved().all(|cell| {
+        let removed = extras.iter().filter(|extra| extra.cell() == cell).count();
+        board.count_notes(&cell) - removed == 2
+    });
+
+    let all_twice = Unit::list().all(|unit| {
+        Digit::list().all(|digit| {
+            let removed = extras
+                .iter()
+                .filter(|extra| extra.digit() == digit && unit.cells_set().contains(extra.cell()))
+                .count();
+
+            matches!(count(unit, digit) - removed, 0 | 2)
+        })
+    });
+
+    (all_bivalue && all_twice).then_some(extras)
+}
+
+/// cells containing any of `extras`.
+fn extra_cells(extras: Set<Candidate>) -> Set<Cell> {
+    extras.iter().map(|extra| extra.cell()).collect()
+}
+
+/// the cells with extra candidates all share a unit, and one of the extra
+/// digits must be in one of them. they act as a single cell with the extra
+/// digits as notes, which can form a naked subset with other cells in the unit.
+fn find_bug_type_3(board: &Board) -> StrategyResult {
+    let Some(extras) = find_bug_extras(board) else {
+        return StrategyResult::default();
+    };
+
+    let bug_cells = extra_cells(extras);
+    let extra_digits: Set<Digit> = extras.iter().map(|extra| extra.digit()).collect();
+
+    if bug_cells.len() < 2 {
+        return StrategyResult::default();
+    }
+
+    let units = Unit::list().filter(|unit| bug_cells.is_subset(&unit.cells_set()));
+
+    for unit in units {
+        let others = (unit.cells_set() & board.cells_unsolved()) - bug_cells;
+
+        for size in 1..others.len() {
+            for subset in others.iter().combinations(size) {
+                let digits = subset
+                    .iter()
+                    .map(|cell| *board.get_notes(cell).unwrap())
+                    .fold(extra_digits, |acc, notes| acc | notes);
+
+                if digits.len() != size + 1 {
+                    continue;
+                }
+
+                let subset: Set<Cell> = subset.into_iter().collect();
+
+                let candidates_in = |cells: Set<Cell>| -> Set<Candidate> {
+                    cells
+                        .iter()
+                        .flat_map(|cell| {
+                            (*board.get_notes(&cell).unwrap() & digits).map(|d| (cell, d).into())
+                        })
+                        .collect()
+                };
+
+                let eliminations = candidates_in(others - subset);
+
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return StrategyResult {
+                    eliminations,
+                    highlights: extras,
+                    highlights2: candidates_in(subset),
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// two cells sharing a unit have all the extra candidates, and a digit which
+/// appears in that unit only in those two cells. one of the cells must be that
+/// digit and the other one of its extra digits, so every other note can be
+/// eliminated from both.
+fn find_bug_type_4(board: &Board) -> StrategyResult {
+    let Some(extras) = find_bug_extras(board) else {
+        return StrategyResult::default();
+    };
+
+    let bug_cells = extra_cells(extras);
+
+    let Ok([p, q]) = bug_cells.try_into_array() else {
+        return StrategyResult::default();
+    };
+
+    let shared = (*board.get_notes(&p).unwrap() & *board.get_notes(&q).unwrap())
+        - extras.map(|extra| extra.digit());
+
+    for unit in Unit::list().filter(|unit| bug_cells.is_subset(&unit.cells_set())) {
+        for digit in shared {
+            if unit.cells_set() & board.cells_with_note(digit) != bug_cells {
+                continue;
+            }
+
+            let keep = extras | bug_cells.map(|cell| (cell, digit).into());
+
+            let eliminations: Set<Candidate> = bug_cells
+                .iter()
+                .flat_map(|cell| board.get_notes(&cell).unwrap().map(|d| (cell, d).into()))
+                .collect::<Set<Candidate>>()
+                - keep;
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            return StrategyResult {
+                eliminations,
+                highlights: extras,
+                highlights2: bug_cells.map(|cell| (cell, digit).into()),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, board_before, candidates,
+    };
+
+    /// a bug with two extra candidates, both 2s in the same column.
+    const TWO_EXTRAS: &str =
+        ".3.....618...4..5....67....54..31.......9..8..1...7...4...6.9....9.....5..73.....";
+
+    #[test]
+    fn bug_plus_1() {
+        let board = board_before(
+            ".2...95.4..5.....3.3...82.....9.......83.4.....4.....1.827...1.1....5..66...2...9",
+            BUG_PLUS_1,
+        );
+
+        let result = (BUG_PLUS_1.find)(&board);
+
+        assert_eq!(result.solutions, candidates("D8#3"));
+    }
+
+    #[test]
+    fn bug_extras() {
+        let board = board_before(TWO_EXTRAS, BUG_TYPE_2);
+
+        assert_eq!(find_bug_extras(&board), Some(candidates("H8#2 I8#2")));
+
+        // with more than one extra candidate, none of them can be placed
+        assert_finds_nothing(&board, &[BUG_PLUS_1]);
+    }
+
+    #[test]
+    fn bug_type_2() {
+        let board = board_before(TWO_EXTRAS, BUG_TYPE_2);
+
+        let result = assert_eliminations(BUG_TYPE_2, &board, "C8#2 G8#2 I9#2");
+
+        assert_eq!(result.highlights, candidates("H8#2 I8#2"));
+    }
+
+    #[test]
+    fn bug_type_3() {
+        let board = board_before(TWO_EXTRAS, BUG_TYPE_3);
+
+        // H8 and I8 act as a cell with the single note 2, forming a naked pair
+        // with C8
+        let result = assert_eliminations(BUG_TYPE_3, &board, "G8#2");
+
+        assert_eq!(result.highlights, candidates("H8#2 I8#2"));
+        assert_eq!(result.highlights2, candidates("C8#2 C8#4"));
+    }
+
+    #[test]
+    fn bug_type_4() {
+        let board = board_before(TWO_EXTRAS, BUG_TYPE_4);
+
+        // 1 appears in the column only in H8 and I8
+        let result = assert_eliminations(BUG_TYPE_4, &board, "H8#7 I8#4");
+
+        assert_eq!(result.highlights, candidates("H8#2 I8#2"));
+        assert_eq!(result.highlights2, candidates("H8#1 I8#1"));
+    }
+
+    #[test]
+    fn no_bug() {
+        let board = basic_board(
+            "..........6...1....518........7..2.67...6.1.31....85..3279....49...........4..3..",
+        );
+
+        assert_eq!(find_bug_extras(&board), None);
+        assert_finds_nothing(&board, &[BUG_PLUS_1, BUG_TYPE_2, BUG_TYPE_3, BUG_TYPE_4]);
+    }
+}
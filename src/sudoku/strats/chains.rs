@@ -1,40 +1,93 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
 
 use crate::{
     bitset::Set,
-    sudoku::{Board, Candidate},
+    sudoku::{Board, Candidate, Cell, Digit},
+    util::TryIntoArray,
 };
 
 use super::{
-    link::{Bilocal, Bivalue, LinkClass, LinkGraph, Strong, Weak, WeakUnit},
+    link::{
+        AlsStrong, AlsWeak, Bilocal, Bivalue, GroupedBilocal, GroupedStrong, GroupedWeak,
+        GroupedWeakUnit, LinkClass, LinkGraph, Strong, Vertex, Weak, WeakUnit,
+    },
     Strategy, StrategyResult,
 };
 
 // =============================================================================
 
+pub const REMOTE_PAIRS: Strategy = Strategy {
+    name: "Remote Pairs",
+    difficulty: 110,
+    find: find_remote_pairs,
+};
+
 pub const X_CHAIN_SIMPLE: Strategy = Strategy {
     name: "X-Chain Simple",
+    difficulty: 260,
     find: find_chain::<Bilocal, Bilocal>,
 };
 
 pub const X_CHAIN: Strategy = Strategy {
     name: "X-Chain",
+    difficulty: 260,
     find: find_chain::<Bilocal, WeakUnit>,
 };
 
 pub const XY_CHAIN: Strategy = Strategy {
     name: "XY-Chain",
+    difficulty: 260,
     find: find_chain::<Bivalue, WeakUnit>,
 };
 
 pub const AIC: Strategy = Strategy {
     name: "AIC",
+    difficulty: 280,
     find: find_chain::<Strong, Weak>,
 };
 
+pub const GROUPED_X_CHAIN: Strategy = Strategy {
+    name: "Grouped X-Chain",
+    difficulty: 280,
+    find: find_chain::<GroupedBilocal, GroupedWeakUnit>,
+};
+
+pub const GROUPED_AIC: Strategy = Strategy {
+    name: "Grouped AIC",
+    difficulty: 300,
+    find: find_chain::<GroupedStrong, GroupedWeak>,
+};
+
+pub const ALS_AIC: Strategy = Strategy {
+    name: "ALS-AIC",
+    difficulty: 340,
+    find: find_chain::<AlsStrong, AlsWeak>,
+};
+
 // =============================================================================
 
-fn find_chain<S: LinkClass, W: LinkClass>(board: &Board) -> StrategyResult {
+/// finds the shortest useful alternating chain, where links from `S` are used
+/// as strong links and links from `W` as weak links.
+///
+/// chains are searched for breadth first from every vertex, both starting
+/// with a strong link and starting with a weak link. an open chain starting
+/// and ending with strong links means one of its ends is true. a chain which
+/// returns to its start can also be a nice loop:
+///
+/// - continuous: strong and weak links alternate all the way round, so every
+///   weak link in the loop has exactly one true end.
+/// - discontinuous with two strong links at the start: the start being false
+///   would force it to be true, so it is true.
+/// - discontinuous with two weak links at the start: the start being true
+///   would force it to be false, so it is false.
+fn find_chain<S, W>(board: &Board) -> StrategyResult
+where
+    S: LinkClass,
+    S::Vertex: Vertex,
+    W: LinkClass<Vertex = S::Vertex>,
+{
     let mut chains = Vec::new();
 
     let strong_links = LinkGraph::new::<S>(board);
@@ -45,62 +98,70 @@ fn find_chain<S: LinkClass, W: LinkClass>(board: &Board) -> StrategyResult {
         Parity::Odd => &weak_links,
     };
 
-    for root_cell in board.iter_unsolved() {
-        for root_digit in board.get_notes(&root_cell).unwrap().iter() {
-            let root = (root_cell, root_digit).into();
-
-            let mut queue = VecDeque::<QueueItem>::new();
-            let mut visited = Set::<Candidate>::new();
-            let mut parents = HashMap::<Candidate, Candidate>::new();
+    for root in S::Vertex::all(board) {
+        for root_parity in [Parity::Even, Parity::Odd] {
+            let mut queue = VecDeque::<QueueItem<S::Vertex>>::new();
+            let mut visited = HashSet::<S::Vertex>::new();
+            let mut parents = HashMap::<(S::Vertex, Parity), S::Vertex>::new();
 
-            queue.push_back(QueueItem::new(root, Parity::Even));
+            queue.push_back(QueueItem::new(root, root_parity));
 
             while let Some(u_item) = queue.pop_front() {
                 let u = u_item.vertex;
                 let u_parity = u_item.parity;
 
-                if visited.contains(u) {
+                if visited.contains(&u) {
                     continue;
                 }
 
                 // check if chain is nontrivial and useful
-                if u_parity == Parity::Odd {
-                    // type 1: same digit on both ends
-                    if u.digit() == root.digit() {
-                        let digit = u.digit();
-
-                        let common_neighbors = u.cell().neighbors() & root.cell().neighbors();
-
-                        let eliminations = (common_neighbors & board.cells_with_note(digit))
-                            .map(|cell| (cell, digit).into());
-
-                        if !eliminations.is_empty() {
-                            let chain = backtrack_chain(u, &parents);
-
-                            let mut highlights = Set::new();
-                            let mut highlights2 = Set::new();
-
-                            for (i, &c) in chain.iter().enumerate() {
-                                if i % 2 == 0 {
-                                    highlights.insert(c);
-                                } else {
-                                    highlights2.insert(c);
-                                }
+                let result = match (root_parity, u_parity) {
+                    // root false => u true
+                    (Parity::Even, Parity::Odd) => {
+                        let closing_chain = weak_links
+                            .neighbors(&u)
+                            .contains(&root)
+                            .then(|| backtrack_chain(u, u_parity, &parents))
+                            .filter(|chain| is_continuous_loop(chain, &strong_links, &weak_links));
+
+                        if let Some(chain) = closing_chain {
+                            let eliminations = loop_eliminations(board, &chain);
+                            chain_result(chain, Set::new(), eliminations)
+                        } else {
+                            let eliminations = chain_eliminations(board, root, u);
+
+                            if eliminations.is_empty() {
+                                None
+                            } else {
+                                let chain = backtrack_chain(u, u_parity, &parents);
+                                chain_result(chain, Set::new(), eliminations)
                             }
-
-                            chains.push((
-                                chain,
-                                StrategyResult {
-                                    eliminations,
-                                    highlights,
-                                    highlights2,
-                                    ..Default::default()
-                                },
-                            ));
                         }
                     }
+                    // root false => u false
+                    (Parity::Even, Parity::Even) if u != root => {
+                        if strong_links.neighbors(&u).contains(&root) {
+                            let chain = backtrack_chain(u, u_parity, &parents);
+                            let (solutions, eliminations) = vertex_true(board, root);
+                            chain_result(chain, solutions, eliminations)
+                        } else {
+                            None
+                        }
+                    }
+                    // root true => u true
+                    (Parity::Odd, Parity::Odd) if u != root => {
+                        if weak_links.neighbors(&u).contains(&root) {
+                            let chain = backtrack_chain(u, u_parity, &parents);
+                            chain_result(chain, Set::new(), root.candidates())
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                };
 
-                    // type 2: different digits which see each other
+                if let Some(result) = result {
+                    chains.push(result);
                 }
 
                 visited.insert(u_item.vertex);
@@ -108,18 +169,20 @@ fn find_chain<S: LinkClass, W: LinkClass>(board: &Board) -> StrategyResult {
                 let neighbors = links(u_parity).neighbors(&u);
 
                 for v in neighbors {
-                    if visited.contains(v) {
+                    if visited.contains(&v) {
                         continue;
                     }
 
-                    parents.insert(v, u);
+                    // keep the first parent, which is the one the vertex is
+                    // first popped with
+                    parents.entry((v, u_parity.flip())).or_insert(u);
                     queue.push_back(QueueItem::new(v, u_parity.flip()));
                 }
             }
         }
     }
 
-    let minimum = chains.into_iter().min_by_key(|(chain, _)| chain.len());
+    let minimum = chains.into_iter().min_by_key(|(length, _)| *length);
 
     if let Some(minimum) = minimum {
         return minimum.1;
@@ -128,9 +191,223 @@ fn find_chain<S: LinkClass, W: LinkClass>(board: &Board) -> StrategyResult {
     StrategyResult::default()
 }
 
+/// finds a chain of at least four bivalue cells with the same two notes, each
+/// seeing the next. the cells alternate between the two digits along the
+/// chain, so any two cells of opposite parity hold both digits between them,
+/// which are eliminated from every cell seeing both.
+fn find_remote_pairs(board: &Board) -> StrategyResult {
+    let mut visited = Set::<Cell>::new();
+
+    for root in board.iter_unsolved() {
+        let notes = *board.get_notes(&root).unwrap();
+
+        if notes.len() != 2 || visited.contains(root) {
+            continue;
+        }
+
+        let pair_cells: Set<Cell> = board
+            .iter_unsolved()
+            .filter(|cell| board.get_notes(cell) == Some(&notes))
+            .collect();
+
+        let mut parities = [Set::new(), Set::new()];
+        let mut stack = vec![(root, 0)];
+
+        while let Some((u, parity)) = stack.pop() {
+            if visited.contains(u) {
+                continue;
+            }
+
+            visited.insert(u);
+            parities[parity].insert(u);
+
+            for v in u.neighbors() & pair_cells {
+                stack.push((v, 1 - parity));
+            }
+        }
+
+        let chain = parities[0] | parities[1];
+
+        if chain.len() < 4 {
+            continue;
+        }
+
+        let elim_cells = parities[0]
+            .iter()
+            .cartesian_product(parities[1].iter())
+            .map(|(p, q)| p.neighbors() & q.neighbors())
+            .sum::<Set<Cell>>()
+            - chain;
+
+        let eliminations: Set<Candidate> = notes
+            .iter()
+            .map(|digit| {
+                (elim_cells & board.cells_with_note(digit)).map(|cell| (cell, digit).into())
+            })
+            .sum();
+
+        if eliminations.is_empty() {
+            continue;
+        }
+
+        let candidates = |cells: Set<Cell>| -> Set<Candidate> {
+            cells
+                .iter()
+                .flat_map(|cell| notes.map(|digit| (cell, digit).into()))
+                .collect()
+        };
+
+        return StrategyResult {
+            eliminations,
+            highlights: candidates(parities[0]),
+            highlights2: candidates(parities[1]),
+            ..Default::default()
+        };
+    }
+
+    StrategyResult::default()
+}
+
+/// pairs the length of a chain with the result it gives, highlighting
+/// alternate vertices along it, or returns `None` if the result is trivial.
+fn chain_result<V: Vertex>(
+    chain: Vec<V>,
+    solutions: Set<Candidate>,
+    eliminations: Set<Candidate>,
+) -> Option<(usize, StrategyResult)> {
+    if solutions.is_empty() && eliminations.is_empty() {
+        return None;
+    }
+
+    let mut highlights = Set::new();
+    let mut highlights2 = Set::new();
+
+    for (i, &v) in chain.iter().enumerate() {
+        if i % 2 == 0 {
+            highlights |= v.candidates();
+        } else {
+            highlights2 |= v.candidates();
+        }
+    }
+
+    Some((
+        chain.len(),
+        StrategyResult {
+            solutions,
+            eliminations,
+            highlights,
+            highlights2,
+            ..Default::default()
+        },
+    ))
+}
+
+/// solutions and eliminations from knowing `vertex` is true. a single
+/// candidate is solved, while for a group the digit is eliminated from every
+/// cell which sees the whole group.
+fn vertex_true<V: Vertex>(board: &Board, vertex: V) -> (Set<Candidate>, Set<Candidate>) {
+    let candidates = vertex.candidates();
+
+    if candidates.len() == 1 {
+        return (candidates, Set::new());
+    }
+
+    let (cells, digit) = cells_and_digit(candidates);
+
+    (Set::new(), seen_by_all(board, cells, digit))
+}
+
+/// returns `true` if `chain` closes into a continuous loop: it has an even
+/// number of at least four vertices, and links alternate strong and weak all
+/// the way round, starting with a strong link and ending with a weak link back
+/// to the start.
+fn is_continuous_loop<V: Vertex>(
+    chain: &[V],
+    strong_links: &LinkGraph<V>,
+    weak_links: &LinkGraph<V>,
+) -> bool {
+    chain.len() >= 4
+        && chain.len().is_multiple_of(2)
+        && (0..chain.len()).all(|i| {
+            let links = if i % 2 == 0 { strong_links } else { weak_links };
+            links
+                .neighbors(&chain[i])
+                .contains(&chain[(i + 1) % chain.len()])
+        })
+}
+
+/// eliminations from a continuous loop, which starts with a strong link and
+/// ends with a weak link back to the start. every weak link in the loop has
+/// exactly one true end, so anything seeing both ends is eliminated.
+fn loop_eliminations<V: Vertex>(board: &Board, chain: &[V]) -> Set<Candidate> {
+    (1..chain.len())
+        .step_by(2)
+        .map(|i| chain_eliminations(board, chain[i], chain[(i + 1) % chain.len()]))
+        .sum()
+}
+
+/// eliminations from a chain where at least one of `start` and `end` is true.
+fn chain_eliminations<V: Vertex>(board: &Board, start: V, end: V) -> Set<Candidate> {
+    let (start_cells, start_digit) = cells_and_digit(start.candidates());
+    let (end_cells, end_digit) = cells_and_digit(end.candidates());
+
+    // type 1: same digit on both ends
+    if start_digit == end_digit {
+        return seen_by_all(board, start_cells | end_cells, start_digit);
+    }
+
+    // type 2: different digits in the same cell, so the cell must be one of
+    // them
+    if let (Ok(start_cell), Ok(end_cell)) = (start_cells.try_singleton(), end_cells.try_singleton())
+    {
+        if start_cell == end_cell {
+            let notes = *board.get_notes(&start_cell).unwrap();
+
+            return (notes - Set::from([start_digit, end_digit].as_slice()))
+                .map(|digit| (start_cell, digit).into());
+        }
+    }
+
+    // type 2: different digits which see each other, so a single cell at one
+    // end can't be the digit at the other end
+    [
+        (start_cells, end_cells, end_digit),
+        (end_cells, start_cells, start_digit),
+    ]
+    .into_iter()
+    .filter_map(|(cells, other_cells, other_digit)| {
+        let cell = cells.try_singleton().ok()?;
+
+        (other_cells
+            .iter()
+            .all(|other| cell.neighbors().contains(other))
+            && board.has_note(&cell, other_digit))
+        .then(|| (cell, other_digit).into())
+    })
+    .collect()
+}
+
+/// cells and digit of a vertex's candidates, which always share a digit.
+fn cells_and_digit(candidates: Set<Candidate>) -> (Set<Cell>, Digit) {
+    let cells = candidates.map(|candidate| candidate.cell());
+    let digit = candidates.iter().next().unwrap().digit();
+
+    (cells, digit)
+}
+
+/// candidates of `digit` in cells which see every cell in `cells`.
+fn seen_by_all(board: &Board, cells: Set<Cell>, digit: Digit) -> Set<Candidate> {
+    cells
+        .iter()
+        .fold(board.cells_with_note(digit), |acc, cell| {
+            acc & cell.neighbors()
+        })
+        .map(|cell| (cell, digit).into())
+}
+
 // -----------------------------------------------------------------------------
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 enum Parity {
     Even,
     Odd,
@@ -147,28 +424,188 @@ impl Parity {
 
 // -----------------------------------------------------------------------------
 
-struct QueueItem {
-    vertex: Candidate,
+struct QueueItem<V> {
+    vertex: V,
     parity: Parity,
 }
 
-impl QueueItem {
-    fn new(vertex: Candidate, parity: Parity) -> Self {
+impl<V> QueueItem<V> {
+    fn new(vertex: V, parity: Parity) -> Self {
         Self { vertex, parity }
     }
 }
 
 // -----------------------------------------------------------------------------
 
-fn backtrack_chain(u: Candidate, parents: &HashMap<Candidate, Candidate>) -> Vec<Candidate> {
+/// follows the parents of `u`, reached with `parity`, back to the root. each
+/// parent was reached with the opposite parity, so links along the chain
+/// alternate.
+fn backtrack_chain<V: Vertex>(u: V, parity: Parity, parents: &HashMap<(V, Parity), V>) -> Vec<V> {
     let mut chain = vec![u];
 
-    let mut v = u;
-    while let Some(parent) = parents.get(&v) {
-        chain.push(*parent);
-        v = *parent;
+    let mut v = (u, parity);
+    while let Some(&parent) = parents.get(&v) {
+        chain.push(parent);
+        v = (parent, v.1.flip());
     }
 
     chain.reverse();
     chain
 }
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::{
+        strats::test_util::{
+            assert_eliminations, assert_finds_nothing, basic_board, board_before, candidates,
+        },
+        Profile, Solver,
+    };
+
+    const NO_CHAIN: &str =
+        "..........6...1....518........7..2.67...6.1.31....85..3279....49...........4..3..";
+
+    fn candidate(candidate: &str) -> Candidate {
+        candidates(candidate).try_singleton().ok().unwrap()
+    }
+
+    #[test]
+    fn remote_pairs() {
+        let board = basic_board(
+            "..25..1..5.6..8329.3........79.3.2..3...81.5...1..........9.83.......512.....7...",
+        );
+
+        assert_eliminations(REMOTE_PAIRS, &board, "D8#4 F7#4");
+    }
+
+    #[test]
+    fn no_remote_pairs() {
+        let board = basic_board(NO_CHAIN);
+
+        assert_finds_nothing(&board, &[REMOTE_PAIRS]);
+    }
+
+    #[test]
+    fn x_chain() {
+        let board = basic_board(
+            ".91.6..........4..3.27.....6...1.........592..57..86..9....4..61..3.7.......2...3",
+        );
+
+        assert_eliminations(X_CHAIN, &board, "D4#4");
+    }
+
+    #[test]
+    fn xy_chain() {
+        let board = basic_board(
+            "...7..28..3..1............97..59..6..1..2.4.58.........5.9.7..2....8....69...5..1",
+        );
+
+        assert_eliminations(XY_CHAIN, &board, "A3#1 G1#1");
+    }
+
+    #[test]
+    fn xy_chain_continuous_loop() {
+        let board = basic_board(
+            ".91.6..........4..3.27.....6...1.........592..57..86..9....4..61..3.7.......2...3",
+        );
+
+        // four bivalue cells, each weak link eliminating from cells seeing
+        // both of its ends
+        let result = assert_eliminations(XY_CHAIN, &board, "A8#3 D8#3");
+
+        assert_eq!(result.highlights.len(), 4);
+        assert_eq!(result.highlights2.len(), 4);
+    }
+
+    #[test]
+    fn aic() {
+        let board = basic_board(
+            "92....6.86..2....1.3......7.......7....1..83..6783...2.....9....5..4.....9356.2..",
+        );
+
+        assert_eliminations(AIC, &board, "C5#5");
+    }
+
+    #[test]
+    fn grouped_x_chain() {
+        let board = board_before(
+            "..56....8....4..93.42......18..2..5......8....24....1....8..7.6..6.59.84.....6...",
+            GROUPED_X_CHAIN,
+        );
+
+        // the chain only exists through H1 and H2 acting as one end of a link
+        assert_finds_nothing(&board, &[X_CHAIN, AIC]);
+
+        let result = assert_eliminations(GROUPED_X_CHAIN, &board, "D4#3");
+
+        assert_eq!(result.highlights, candidates("D3#3 H1#3 H2#3"));
+        assert_eq!(result.highlights2, candidates("G3#3 H4#3"));
+
+        // the same group works as a node for alternating chains
+        assert_eliminations(GROUPED_AIC, &board, "D4#3");
+    }
+
+    #[test]
+    fn als_aic() {
+        let board = board_before(
+            ".7.3...8...2...1...68..1.2.3.5.97........2....2....93...6.7.8.2.....6.4....8...5.",
+            ALS_AIC,
+        );
+
+        assert_eliminations(ALS_AIC, &board, "B1#9 B9#9");
+    }
+
+    #[test]
+    fn no_chain() {
+        let board = basic_board(NO_CHAIN);
+
+        assert_finds_nothing(&board, &[X_CHAIN, XY_CHAIN, AIC]);
+    }
+
+    #[test]
+    fn chain_ends_in_same_cell() {
+        let board = basic_board(NO_CHAIN);
+        let [start, end] = ["A1#2", "A1#4"].map(candidate);
+
+        // A1 is {2, 4, 8}, so it must be one of the ends
+        assert_eq!(chain_eliminations(&board, start, end), candidates("A1#8"));
+    }
+
+    #[test]
+    fn chain_ends_see_each_other() {
+        let board = basic_board(NO_CHAIN);
+
+        // A1 and B1 share a block and both have notes 2 and 4
+        let [start, end] = ["A1#2", "B1#4"].map(candidate);
+        assert_eq!(
+            chain_eliminations(&board, start, end),
+            candidates("A1#4 B1#2")
+        );
+
+        // A3 is {3, 9}, so only A5 loses a digit
+        let [start, end] = ["A3#9", "A5#2"].map(candidate);
+        assert_eq!(chain_eliminations(&board, start, end), candidates("A5#9"));
+    }
+
+    #[test]
+    fn loops_alternate() {
+        // a vertex first queued from one parent and queued again from another
+        // used to take the later parent, so a loop could be backtracked which
+        // didn't alternate, eliminating the true C5#6
+        const PUZZLE: &str =
+            "...5..1..81..94....4....5.....3..4..6...8.....5...9..6..2..8..5.93..5..4...72....";
+
+        let basic = Profile::from_name("basic").unwrap();
+
+        let mut solver = Solver::new();
+        solver.set_strategies(&[basic.strategies, &[XY_CHAIN]].concat());
+        solver.set_verify(true);
+        solver.load_board_string(PUZZLE);
+        solver.solve();
+
+        assert_eq!(solver.violations, vec![]);
+    }
+}
@@ -0,0 +1,281 @@
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{Board, Candidate},
+};
+
+use super::{
+    link::{Bilocal, LinkClass, LinkGraph, Strong, Vertex},
+    Strategy, StrategyResult,
+};
+
+// =============================================================================
+
+pub const SIMPLE_COLORS: Strategy = Strategy {
+    name: "Simple Colors",
+    difficulty: 150,
+    find: |board| find_colors::<Bilocal>(board, false),
+};
+
+pub const MULTI_COLORS: Strategy = Strategy {
+    name: "Multi Colors",
+    difficulty: 200,
+    find: find_multi_colors,
+};
+
+pub const MEDUSA_3D: Strategy = Strategy {
+    name: "3D Medusa",
+    difficulty: 320,
+    find: |board| find_colors::<Strong>(board, true),
+};
+
+// =============================================================================
+
+/// connected components of the graph of `L` with at least one link, each
+/// split into two colors so that every link joins opposite colors. since
+/// the links are conjugate, exactly one color of each component is true.
+fn color_components<L>(board: &Board) -> Vec<[Set<Candidate>; 2]>
+where
+    L: LinkClass<Vertex = Candidate>,
+{
+    let links = LinkGraph::new::<L>(board);
+
+    let mut colored = Set::new();
+    let mut components = Vec::new();
+
+    for root in Candidate::all(board) {
+        if colored.contains(root) || links.neighbors(&root).is_empty() {
+            continue;
+        }
+
+        let mut colors = [Set::new(), Set::new()];
+        let mut stack = vec![(root, 0)];
+
+        while let Some((u, color)) = stack.pop() {
+            if colored.contains(u) {
+                continue;
+            }
+
+            colored.insert(u);
+            colors[color].insert(u);
+
+            for v in links.neighbors(&u) {
+                stack.push((v, 1 - color));
+            }
+        }
+
+        components.push(colors);
+    }
+
+    components
+}
+
+/// returns `true` if `a` and `b` are candidates of the same digit in cells
+/// which see each other.
+fn sees_digit(a: Candidate, b: Candidate) -> bool {
+    a.digit() == b.digit() && a.cell().neighbors().contains(b.cell())
+}
+
+/// returns `true` if `a` and `b` can't both be true, either sharing a digit
+/// in cells which see each other or being different digits in the same cell.
+fn sees_candidate(a: Candidate, b: Candidate) -> bool {
+    sees_digit(a, b) || (a.cell() == b.cell() && a.digit() != b.digit())
+}
+
+/// returns `true` if `candidate` sees any candidate in `color`.
+fn sees_color(
+    candidate: Candidate,
+    color: Set<Candidate>,
+    sees: fn(Candidate, Candidate) -> bool,
+) -> bool {
+    color.iter().any(|other| sees(candidate, other))
+}
+
+// -----------------------------------------------------------------------------
+
+/// colors each component of `L`'s graph and applies the coloring rules,
+/// where candidates see each other within a digit, or also `across_digits`
+/// within a cell:
+///
+/// - color wrap: two candidates of one color see each other, so that color
+///   is false.
+/// - empty cell: across digits, every candidate in a cell without the color
+///   sees it, so that color is false.
+/// - color trap: a candidate outside the component sees both colors, so it is
+///   false.
+fn find_colors<L: LinkClass<Vertex = Candidate>>(
+    board: &Board,
+    across_digits: bool,
+) -> StrategyResult {
+    let candidates = Candidate::all(board);
+    let sees = if across_digits {
+        sees_candidate
+    } else {
+        sees_digit
+    };
+
+    for [a, b] in color_components::<L>(board) {
+        let result = |eliminations| StrategyResult {
+            eliminations,
+            highlights: a,
+            highlights2: b,
+            ..Default::default()
+        };
+
+        for color in [a, b] {
+            if color.iter().tuple_combinations().any(|(p, q)| sees(p, q)) {
+                return result(color);
+            }
+        }
+
+        if across_digits {
+            for color in [a, b] {
+                let empties = board.iter_unsolved().any(|cell| {
+                    board.get_notes(&cell).unwrap().iter().all(|digit| {
+                        let candidate = Candidate::from((cell, digit));
+                        !color.contains(candidate) && sees_color(candidate, color, sees_digit)
+                    })
+                });
+
+                if empties {
+                    return result(color);
+                }
+            }
+        }
+
+        let eliminations: Set<Candidate> = candidates
+            .iter()
+            .copied()
+            .filter(|&candidate| {
+                !(a | b).contains(candidate)
+                    && sees_color(candidate, a, sees)
+                    && sees_color(candidate, b, sees)
+            })
+            .collect();
+
+        if eliminations.is_nonempty() {
+            return result(eliminations);
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// colors the components of each digit's conjugate pairs and compares pairs of
+/// components. if a color `x1` of one sees a color `x2` of the other, they
+/// can't both be true, so one of the opposite colors `y1` and `y2` is:
+///
+/// - type 1: a candidate of `x1` sees both colors of the other component, so
+///   `x1` is false.
+/// - type 2: the digit is eliminated from cells which see both `y1` and `y2`.
+fn find_multi_colors(board: &Board) -> StrategyResult {
+    let components = color_components::<Bilocal>(board);
+    let candidates = Candidate::all(board);
+
+    let digit = |component: [Set<Candidate>; 2]| component[0].iter().next().unwrap().digit();
+
+    for (&p, &q) in components.iter().tuple_combinations() {
+        if digit(p) != digit(q) {
+            continue;
+        }
+
+        let all_colors = p[0] | p[1] | q[0] | q[1];
+
+        for (first, second) in [(p, q), (q, p)] {
+            let result = |eliminations| StrategyResult {
+                eliminations,
+                highlights: first[0] | second[0],
+                highlights2: first[1] | second[1],
+                ..Default::default()
+            };
+
+            for [x1, y1] in [first, [first[1], first[0]]] {
+                let type_1 = x1.iter().any(|candidate| {
+                    sees_color(candidate, second[0], sees_digit)
+                        && sees_color(candidate, second[1], sees_digit)
+                });
+
+                if type_1 {
+                    return result(x1);
+                }
+
+                for [x2, y2] in [second, [second[1], second[0]]] {
+                    if !x1
+                        .iter()
+                        .any(|candidate| sees_color(candidate, x2, sees_digit))
+                    {
+                        continue;
+                    }
+
+                    let eliminations: Set<Candidate> = candidates
+                        .iter()
+                        .copied()
+                        .filter(|&candidate| {
+                            candidate.digit() == digit(p)
+                                && !all_colors.contains(candidate)
+                                && sees_color(candidate, y1, sees_digit)
+                                && sees_color(candidate, y2, sees_digit)
+                        })
+                        .collect();
+
+                    if eliminations.is_nonempty() {
+                        return result(eliminations);
+                    }
+                }
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, board_before,
+    };
+
+    #[test]
+    fn simple_colors() {
+        let board = basic_board(
+            "...528........461...21........4......19.8...4.7...3.9..........9...3.86.6.....941",
+        );
+
+        assert_eliminations(SIMPLE_COLORS, &board, "G7#3");
+    }
+
+    #[test]
+    fn multi_colors() {
+        let board = board_before(
+            ".4....3..6..8.1..7.1..43.8.89..6..7......8.3...7......98...214...1..5....3.......",
+            MULTI_COLORS,
+        );
+
+        assert_eliminations(MULTI_COLORS, &board, "B5#5");
+    }
+
+    #[test]
+    fn medusa_3d() {
+        let board = basic_board(
+            ".6..7..9..45.....88....9..........454.6...7......28...7.9...2..5..7......8..5.41.",
+        );
+
+        // colors on a single digit find nothing here
+        assert_finds_nothing(&board, &[SIMPLE_COLORS]);
+
+        assert_eliminations(MEDUSA_3D, &board, "A6#3");
+    }
+
+    #[test]
+    fn no_colors() {
+        let board = basic_board(
+            "92....6.86..2....1.3......7.......7....1..83..6783...2.....9....5..4.....9356.2..",
+        );
+
+        assert_finds_nothing(&board, &[SIMPLE_COLORS, MULTI_COLORS, MEDUSA_3D]);
+    }
+}
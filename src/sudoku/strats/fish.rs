@@ -1,8 +1,8 @@
 use itertools::Itertools;
 
 use crate::{
-    bitset::Set,
-    sudoku::{pos::UnitClass, Board, Cell, Col, Digit, Row},
+    bitset::{Element, Set},
+    sudoku::{pos::UnitClass, Board, Cell, Col, Digit, Row, Unit, UnitType},
 };
 
 use super::{Strategy, StrategyResult};
@@ -11,18 +11,69 @@ use super::{Strategy, StrategyResult};
 
 pub const X_WING: Strategy = Strategy {
     name: "X-Wing",
+    difficulty: 140,
     find: find_basic_fish::<2>,
 };
 
 pub const SWORDFISH: Strategy = Strategy {
     name: "Swordfish",
+    difficulty: 150,
     find: find_basic_fish::<3>,
 };
 pub const JELLYFISH: Strategy = Strategy {
     name: "Jellyfish",
+    difficulty: 160,
     find: find_basic_fish::<4>,
 };
 
+pub const FINNED_X_WING: Strategy = Strategy {
+    name: "Finned X-Wing",
+    difficulty: 130,
+    find: |board| find_finned_fish::<2>(board, false),
+};
+
+pub const SASHIMI_X_WING: Strategy = Strategy {
+    name: "Sashimi X-Wing",
+    difficulty: 150,
+    find: |board| find_finned_fish::<2>(board, true),
+};
+
+pub const FINNED_SWORDFISH: Strategy = Strategy {
+    name: "Finned Swordfish",
+    difficulty: 200,
+    find: |board| find_finned_fish::<3>(board, false),
+};
+
+pub const SASHIMI_SWORDFISH: Strategy = Strategy {
+    name: "Sashimi Swordfish",
+    difficulty: 240,
+    find: |board| find_finned_fish::<3>(board, true),
+};
+
+pub const FINNED_JELLYFISH: Strategy = Strategy {
+    name: "Finned Jellyfish",
+    difficulty: 250,
+    find: |board| find_finned_fish::<4>(board, false),
+};
+
+pub const SASHIMI_JELLYFISH: Strategy = Strategy {
+    name: "Sashimi Jellyfish",
+    difficulty: 260,
+    find: |board| find_finned_fish::<4>(board, true),
+};
+
+pub const FRANKEN_FISH: Strategy = Strategy {
+    name: "Franken Fish",
+    difficulty: 300,
+    find: find_franken_fish,
+};
+
+pub const MUTANT_FISH: Strategy = Strategy {
+    name: "Mutant Fish",
+    difficulty: 450,
+    find: find_mutant_fish,
+};
+
 // =============================================================================
 
 fn find_basic_fish<const N: usize>(board: &Board) -> StrategyResult {
@@ -35,6 +86,16 @@ fn find_basic_fish<const N: usize>(board: &Board) -> StrategyResult {
     find_fish::<N, Col, Row>(board)
 }
 
+fn find_finned_fish<const N: usize>(board: &Board, sashimi: bool) -> StrategyResult {
+    let result = find_fish_with_fins::<N, Row, Col>(board, sashimi);
+
+    if result.is_nontrivial() {
+        return result;
+    }
+
+    find_fish_with_fins::<N, Col, Row>(board, sashimi)
+}
+
 fn find_fish<const N: usize, Base: UnitClass, Cover: UnitClass>(board: &Board) -> StrategyResult {
     for x in Digit::list() {
         for base_cells in Base::iter_all()
@@ -74,3 +135,394 @@ fn find_fish<const N: usize, Base: UnitClass, Cover: UnitClass>(board: &Board) -
 
     StrategyResult::default()
 }
+
+/// finds a fish where the base units have extra candidates outside the cover
+/// units, called fins, which all lie in one block. either a fin is true, or the
+/// fish is, so the digit can be eliminated from cover cells which see every
+/// fin.
+///
+/// the fish is sashimi if some base unit has only one candidate left once the
+/// fins are removed, so that it would be degenerate without them.
+fn find_fish_with_fins<const N: usize, Base: UnitClass, Cover: UnitClass>(
+    board: &Board,
+    sashimi: bool,
+) -> StrategyResult {
+    for x in Digit::list() {
+        let x_cells = board.cells_with_note(x);
+
+        let cover_sets = Cover::iter_all()
+            .map(|unit| unit.cells_set() & x_cells)
+            .filter(Set::is_nonempty)
+            .combinations(N)
+            .map(|units| units.into_iter().sum::<Set<Cell>>())
+            .collect_vec();
+
+        for base_units in Base::iter_all()
+            .map(|unit| unit.cells_set() & x_cells)
+            .filter(Set::is_nonempty)
+            .combinations(N)
+        {
+            let base_cells: Set<Cell> = base_units.iter().copied().sum();
+
+            for &cover_cells in &cover_sets {
+                let fins = base_cells - cover_cells;
+
+                if fins.is_empty() || fins.iter().map(|cell| cell.block()).unique().count() != 1 {
+                    continue;
+                }
+
+                let body_sizes = base_units
+                    .iter()
+                    .map(|&unit| (unit - fins).len())
+                    .collect_vec();
+
+                if body_sizes.contains(&0) || sashimi != body_sizes.contains(&1) {
+                    continue;
+                }
+
+                let elim_set = fins
+                    .iter()
+                    .fold(cover_cells - base_cells, |acc, fin| acc & fin.neighbors());
+
+                if elim_set.is_empty() {
+                    continue;
+                }
+
+                return StrategyResult {
+                    eliminations: elim_set.map(|cell| (cell, x).into()),
+                    highlights: (base_cells - fins).map(|cell| (cell, x).into()),
+                    highlights2: fins.map(|cell| (cell, x).into()),
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// -----------------------------------------------------------------------------
+
+/// finds franken fish of up to four units, where the base units are rows and
+/// blocks and the cover units are columns and blocks, or the other way round.
+fn find_franken_fish(board: &Board) -> StrategyResult {
+    let has_block = |base: &[Unit], cover: &[Unit]| base.iter().chain(cover).any(is_block);
+
+    for size in 2..=4 {
+        let result = find_mixed_fish(board, size, is_row_or_block, is_col_or_block, has_block);
+
+        if result.is_nontrivial() {
+            return result;
+        }
+
+        let result = find_mixed_fish(board, size, is_col_or_block, is_row_or_block, has_block);
+
+        if result.is_nontrivial() {
+            return result;
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// finds mutant fish of up to four units, with any mix of units which doesn't
+/// make a basic or franken fish.
+fn find_mutant_fish(board: &Board) -> StrategyResult {
+    let is_mutant = |base: &[Unit], cover: &[Unit]| {
+        let franken = |base_type: fn(&Unit) -> bool, cover_type: fn(&Unit) -> bool| {
+            base.iter().all(base_type) && cover.iter().all(cover_type)
+        };
+
+        !franken(is_row_or_block, is_col_or_block) && !franken(is_col_or_block, is_row_or_block)
+    };
+
+    for size in 2..=4 {
+        let result = find_mixed_fish(board, size, |_| true, |_| true, is_mutant);
+
+        if result.is_nontrivial() {
+            return result;
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// finds a fish with `size` base units and `size` cover units of any type,
+/// taking base units from those matching `is_base`, cover units from those
+/// matching `is_cover`, and keeping only fish accepted by `is_kind`.
+///
+/// fins are base candidates outside the cover units, along with endo fins,
+/// which are candidates in more than one base unit. either a fin is true, or
+/// each base unit has its own candidate in the cover units, filling every
+/// cover unit. so the digit can be eliminated from cover cells outside the
+/// base which see every fin.
+///
+/// rather than trying every set of cover units, each possible elimination is
+/// tried in turn, and only cover units which cover it along with every base
+/// candidate that doesn't see it are searched for.
+fn find_mixed_fish(
+    board: &Board,
+    size: usize,
+    is_base: fn(&Unit) -> bool,
+    is_cover: fn(&Unit) -> bool,
+    is_kind: impl Fn(&[Unit], &[Unit]) -> bool,
+) -> StrategyResult {
+    for x in Digit::list() {
+        let x_cells = board.cells_with_note(x);
+
+        let x_units = |is_type: fn(&Unit) -> bool| {
+            Unit::iter_all()
+                .filter(is_type)
+                .filter(|unit| (unit.cells_set() & x_cells).is_nonempty())
+                .collect_vec()
+        };
+
+        let base_units = x_units(is_base);
+        let cover_units = x_units(is_cover);
+
+        for base in base_units.into_iter().combinations(size) {
+            let mut base_cells = Set::new();
+            let mut endo_fins = Set::new();
+
+            for unit in &base {
+                let cells = unit.cells_set() & x_cells;
+                endo_fins |= base_cells & cells;
+                base_cells |= cells;
+            }
+
+            if endo_fins.len() > MAX_ENDO_FINS {
+                continue;
+            }
+
+            let targets = endo_fins
+                .iter()
+                .fold(x_cells - base_cells, |acc, fin| acc & fin.neighbors());
+
+            let eligible = cover_units
+                .iter()
+                .copied()
+                .filter(|unit| !base.contains(unit))
+                .collect_vec();
+
+            for target in targets {
+                let must_cover =
+                    (base_cells - endo_fins - target.neighbors()) | Set::singleton(target);
+
+                let mut cover = Vec::new();
+                let accept = |cover: &[Unit]| is_kind(&base, cover);
+
+                if !find_cover(must_cover, &mut cover, size, &eligible, &accept) {
+                    continue;
+                }
+
+                let cover_cells: Set<Cell> =
+                    cover.iter().map(|unit| unit.cells_set() & x_cells).sum();
+
+                let fins = (base_cells - cover_cells) | endo_fins;
+
+                let elim_set = fins
+                    .iter()
+                    .fold(cover_cells - base_cells, |acc, fin| acc & fin.neighbors());
+
+                return StrategyResult {
+                    eliminations: elim_set.map(|cell| (cell, x).into()),
+                    highlights: (base_cells - fins).map(|cell| (cell, x).into()),
+                    highlights2: fins.map(|cell| (cell, x).into()),
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// most endo fins allowed in a fish, since base units overlapping more than
+/// this rarely leave any eliminations, and allowing them makes the search much
+/// slower.
+const MAX_ENDO_FINS: usize = 2;
+
+/// extends `cover` to `size` units from `eligible` which contain every cell in
+/// `uncovered` and are accepted by `accept`, returning `false` if there are
+/// none.
+fn find_cover(
+    uncovered: Set<Cell>,
+    cover: &mut Vec<Unit>,
+    size: usize,
+    eligible: &[Unit],
+    accept: &dyn Fn(&[Unit]) -> bool,
+) -> bool {
+    if cover.len() == size {
+        return uncovered.is_empty() && accept(cover);
+    }
+
+    // give up if even the units covering the most cells can't cover the rest
+    let most_covered: usize = eligible
+        .iter()
+        .map(|unit| (unit.cells_set() & uncovered).len())
+        .sorted_unstable_by(|a, b| b.cmp(a))
+        .take(size - cover.len())
+        .sum();
+
+    if most_covered < uncovered.len() {
+        return false;
+    }
+
+    // once everything is covered, any further units will do, so only try
+    // them in increasing order
+    let options = match uncovered.iter().next() {
+        Some(cell) => eligible
+            .iter()
+            .filter(|unit| unit.cells_set().contains(cell))
+            .copied()
+            .collect_vec(),
+        None => eligible
+            .iter()
+            .filter(|&&unit| cover.last().is_none_or(|last| unit.index() > last.index()))
+            .copied()
+            .collect_vec(),
+    };
+
+    for unit in options {
+        if cover.contains(&unit) {
+            continue;
+        }
+
+        cover.push(unit);
+
+        if find_cover(uncovered - unit.cells_set(), cover, size, eligible, accept) {
+            return true;
+        }
+
+        cover.pop();
+    }
+
+    false
+}
+
+fn is_block(unit: &Unit) -> bool {
+    matches!(unit.unit_type(), UnitType::Block(_))
+}
+
+fn is_row_or_block(unit: &Unit) -> bool {
+    !matches!(unit.unit_type(), UnitType::Col(_))
+}
+
+fn is_col_or_block(unit: &Unit) -> bool {
+    !matches!(unit.unit_type(), UnitType::Row(_))
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board,
+    };
+
+    const NO_FINNED_FISH: &str =
+        "...7..28..3..1............97..59..6..1..2.4.58.........5.9.7..2....8....69...5..1";
+
+    const NO_MIXED_FISH: &str =
+        "....1..7...9...4..5...9.........31........597.27...8...761..92..347....8..2....4.";
+
+    #[test]
+    fn finned_x_wing() {
+        let board = basic_board(
+            "1...6...2..93.......5..96.3.8.2.4..9.5......1....16....6....34....5..1......9..2.",
+        );
+
+        assert_eliminations(FINNED_X_WING, &board, "H6#8");
+    }
+
+    #[test]
+    fn sashimi_x_wing() {
+        let board = basic_board(
+            ".2..5.3....8........5..6.4....8..2..1..52....2....17.38.3.4.....57...4.....93.56.",
+        );
+
+        assert_eliminations(SASHIMI_X_WING, &board, "G4#2");
+    }
+
+    #[test]
+    fn finned_swordfish() {
+        let board = basic_board(
+            "9...5.38.28.6.............94..........29.7...65..2..7.3..8.......7..5.2......4.68",
+        );
+
+        assert_eliminations(FINNED_SWORDFISH, &board, "D7#6");
+    }
+
+    #[test]
+    fn sashimi_swordfish() {
+        let board = basic_board(
+            "..2..5.9.4.8...6.....6...1.....1.48..95..3......8......7....3....17......3..9...6",
+        );
+
+        assert_eliminations(SASHIMI_SWORDFISH, &board, "C6#2");
+    }
+
+    #[test]
+    fn finned_jellyfish() {
+        let board = basic_board(
+            "........3..7.........1..459.62..3.9..9..85.7.5.37....2..6......9..8..5...3......7",
+        );
+
+        assert_eliminations(FINNED_JELLYFISH, &board, "B5#6 B6#6");
+    }
+
+    #[test]
+    fn sashimi_jellyfish() {
+        let board = basic_board(
+            "15.38..4.4....1.....8...2....97...8..6.9........13..2..7......5...8.5......69.8.3",
+        );
+
+        assert_eliminations(SASHIMI_JELLYFISH, &board, "I6#2");
+    }
+
+    #[test]
+    fn no_finned_fish() {
+        let board = basic_board(NO_FINNED_FISH);
+
+        assert_finds_nothing(
+            &board,
+            &[
+                FINNED_X_WING,
+                SASHIMI_X_WING,
+                FINNED_SWORDFISH,
+                SASHIMI_SWORDFISH,
+                FINNED_JELLYFISH,
+                SASHIMI_JELLYFISH,
+            ],
+        );
+    }
+
+    #[test]
+    fn franken_fish() {
+        let board = basic_board(
+            "517.2..9......9.4..8...5.......9.21...3..6.....2....34....5..2..9....7..2...3.9..",
+        );
+
+        assert_eliminations(FRANKEN_FISH, &board, "H1#6");
+    }
+
+    #[test]
+    fn mutant_fish() {
+        let board = basic_board(
+            ".8.........9.5..3.3....8..6.91..57.....7..2.95..8............6.8.56.4.7..7...9..8",
+        );
+
+        // the fish mixes rows and columns on one side, so isn't franken
+        assert_finds_nothing(&board, &[FRANKEN_FISH]);
+
+        assert_eliminations(MUTANT_FISH, &board, "A9#2");
+    }
+
+    #[test]
+    fn no_mixed_fish() {
+        let board = basic_board(NO_MIXED_FISH);
+
+        assert_finds_nothing(&board, &[FRANKEN_FISH, MUTANT_FISH]);
+    }
+}
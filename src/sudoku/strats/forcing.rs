@@ -0,0 +1,417 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Board, Candidate, Cell, Digit, Unit},
+    util::TryIntoArray,
+};
+
+use super::{
+    link::{LinkGraph, Strong, Vertex, Weak},
+    Strategy, StrategyBranch, StrategyResult,
+};
+
+// =============================================================================
+
+pub const CELL_FORCING_CHAIN: Strategy = Strategy {
+    name: "Cell Forcing Chain",
+    difficulty: 500,
+    find: |board| find_forcing_chain(board, cell_premises),
+};
+
+pub const UNIT_FORCING_CHAIN: Strategy = Strategy {
+    name: "Unit Forcing Chain",
+    difficulty: 500,
+    find: |board| find_forcing_chain(board, unit_premises),
+};
+
+pub const DIGIT_FORCING_CHAIN: Strategy = Strategy {
+    name: "Digit Forcing Chain",
+    difficulty: 500,
+    find: |board| find_forcing_chain(board, digit_premises),
+};
+
+// =============================================================================
+
+/// an assumption that a candidate is true or false, starting one branch of a
+/// forcing chain.
+type Premise = (Candidate, bool);
+
+/// tries each set of premises from `premises`, of which exactly one must hold,
+/// following every branch to its consequences. anything implied by every
+/// branch which doesn't end in a contradiction must be true.
+///
+/// the premises are highlighted, along with the candidates on the paths from
+/// them to the deductions. each branch is also kept with its premise and path,
+/// which for a branch ending in a contradiction leads to the contradiction.
+fn find_forcing_chain(board: &Board, premises: fn(&Board) -> Vec<Vec<Premise>>) -> StrategyResult {
+    let implications = Implications::new(board);
+
+    for branches in premises(board) {
+        let outcomes: Vec<Branch> = branches
+            .iter()
+            .map(|&premise| implications.follow(premise))
+            .collect();
+
+        let mut consistent = outcomes.iter().filter(|branch| !branch.is_contradiction());
+
+        let Some(first) = consistent.next() else {
+            continue;
+        };
+
+        let (trues, falses) = consistent
+            .fold((first.trues, first.falses), |(trues, falses), branch| {
+                (trues & branch.trues, falses & branch.falses)
+            });
+
+        let solved: Set<Cell> = trues.map(|candidate| candidate.cell());
+
+        let eliminations: Set<Candidate> = falses
+            .iter()
+            .filter(|candidate| !solved.contains(candidate.cell()))
+            .collect();
+
+        if trues.is_empty() && eliminations.is_empty() {
+            continue;
+        }
+
+        let assumed: Set<Candidate> = branches.iter().map(|&(candidate, _)| candidate).collect();
+
+        let branches: Vec<StrategyBranch> = branches
+            .iter()
+            .zip(&outcomes)
+            .map(|(&(premise, value), branch)| StrategyBranch {
+                premise,
+                value,
+                path: if branch.is_contradiction() {
+                    branch.paths(branch.conflict)
+                } else {
+                    branch.paths(trues | falses)
+                },
+                contradiction: branch.is_contradiction(),
+            })
+            .collect();
+
+        let paths: Set<Candidate> = branches
+            .iter()
+            .filter(|branch| !branch.contradiction)
+            .map(|branch| branch.path)
+            .sum();
+
+        return StrategyResult {
+            solutions: trues,
+            eliminations,
+            highlights: assumed,
+            highlights2: paths - assumed,
+            branches,
+        };
+    }
+
+    StrategyResult::default()
+}
+
+/// for each cell, one premise per note that it is that digit.
+fn cell_premises(board: &Board) -> Vec<Vec<Premise>> {
+    board
+        .iter_unsolved()
+        .map(|cell| {
+            board
+                .get_notes(&cell)
+                .unwrap()
+                .iter()
+                .map(|digit| ((cell, digit).into(), true))
+                .collect()
+        })
+        .collect()
+}
+
+/// for each unit and digit not yet placed in it, one premise per cell of the
+/// unit with the digit that the digit goes there.
+fn unit_premises(board: &Board) -> Vec<Vec<Premise>> {
+    Unit::list()
+        .flat_map(|unit| {
+            Digit::list().filter_map(move |digit| {
+                let cells = unit.cells_set() & board.cells_with_note(digit);
+
+                (cells.len() >= 2).then(|| {
+                    cells
+                        .iter()
+                        .map(|cell| ((cell, digit).into(), true))
+                        .collect()
+                })
+            })
+        })
+        .collect()
+}
+
+/// for each candidate, one premise that it is true and one that it is false.
+fn digit_premises(board: &Board) -> Vec<Vec<Premise>> {
+    Candidate::all(board)
+        .into_iter()
+        .map(|candidate| vec![(candidate, true), (candidate, false)])
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+
+/// the consequences of a premise: candidates which must be true and false if
+/// it holds, along with the candidates each was deduced from. if it leads to a
+/// contradiction, `conflict` holds the candidates which contradict each other.
+struct Branch {
+    trues: Set<Candidate>,
+    falses: Set<Candidate>,
+    causes: HashMap<Candidate, Vec<Candidate>>,
+    conflict: Set<Candidate>,
+}
+
+impl Branch {
+    fn is_contradiction(&self) -> bool {
+        self.conflict.is_nonempty()
+    }
+
+    /// records that `candidate` has `value` because of `causes`, queueing it to
+    /// be followed. returns `None` and records the conflict if it already has
+    /// the opposite value.
+    fn set(
+        &mut self,
+        candidate: Candidate,
+        value: bool,
+        causes: Vec<Candidate>,
+        queue: &mut VecDeque<Premise>,
+    ) -> Option<()> {
+        let (same, opposite) = if value {
+            (&mut self.trues, self.falses)
+        } else {
+            (&mut self.falses, self.trues)
+        };
+
+        if opposite.contains(candidate) {
+            self.conflict = causes.into_iter().chain([candidate]).collect();
+            return None;
+        }
+
+        if !same.contains(candidate) {
+            same.insert(candidate);
+            self.causes.insert(candidate, causes);
+            queue.push_back((candidate, value));
+        }
+
+        Some(())
+    }
+
+    /// every candidate the deductions in `targets` were reached through.
+    fn paths(&self, targets: Set<Candidate>) -> Set<Candidate> {
+        let mut visited = Set::new();
+        let mut stack: Vec<Candidate> = targets.iter().collect();
+
+        while let Some(candidate) = stack.pop() {
+            if visited.contains(candidate) {
+                continue;
+            }
+
+            visited.insert(candidate);
+
+            if let Some(causes) = self.causes.get(&candidate) {
+                stack.extend(causes.iter().copied());
+            }
+        }
+
+        visited
+    }
+}
+
+/// the links and singles used to follow a premise to its consequences.
+struct Implications<'a> {
+    board: &'a Board,
+    strong_links: LinkGraph,
+    weak_links: LinkGraph,
+}
+
+impl<'a> Implications<'a> {
+    fn new(board: &'a Board) -> Self {
+        Self {
+            board,
+            strong_links: LinkGraph::new::<Strong>(board),
+            weak_links: LinkGraph::new::<Weak>(board),
+        }
+    }
+
+    /// follows `premise` until nothing more can be deduced. a true candidate
+    /// makes its weak links false and a false candidate makes its strong links
+    /// true, and a cell or unit left with one place for a digit makes it true.
+    /// stops at the first contradiction, which is recorded in the branch.
+    fn follow(&self, premise: Premise) -> Branch {
+        let mut branch = Branch {
+            trues: Set::new(),
+            falses: Set::new(),
+            causes: HashMap::new(),
+            conflict: Set::new(),
+        };
+
+        self.deduce(premise, &mut branch);
+
+        branch
+    }
+
+    /// adds the consequences of `premise` to `branch`, returning `None` at a
+    /// contradiction.
+    fn deduce(&self, premise: Premise, branch: &mut Branch) -> Option<()> {
+        let mut queue = VecDeque::new();
+
+        branch.set(premise.0, premise.1, Vec::new(), &mut queue)?;
+
+        while !queue.is_empty() {
+            while let Some((u, value)) = queue.pop_front() {
+                let links = if value {
+                    &self.weak_links
+                } else {
+                    &self.strong_links
+                };
+
+                for v in links.neighbors(&u) {
+                    branch.set(v, !value, vec![u], &mut queue)?;
+                }
+            }
+
+            self.follow_singles(branch, &mut queue)?;
+        }
+
+        Some(())
+    }
+
+    /// sets naked and hidden singles left by the branch's false candidates,
+    /// returning `None` if a cell or unit has no place left for a digit.
+    fn follow_singles(&self, branch: &mut Branch, queue: &mut VecDeque<Premise>) -> Option<()> {
+        let board = self.board;
+
+        let groups = board
+            .iter_unsolved()
+            .map(|cell| {
+                board
+                    .get_notes(&cell)
+                    .unwrap()
+                    .map(|digit| (cell, digit).into())
+            })
+            .chain(Unit::list().flat_map(|unit| {
+                Digit::list().map(move |digit| {
+                    (unit.cells_set() & board.cells_with_note(digit))
+                        .map(|cell| (cell, digit).into())
+                })
+            }))
+            .filter(|candidates: &Set<Candidate>| candidates.is_nonempty());
+
+        for candidates in groups {
+            let remaining = candidates - branch.falses;
+
+            if remaining.is_empty() {
+                branch.conflict = candidates;
+                return None;
+            }
+
+            if let Ok(single) = remaining.try_singleton() {
+                let causes = (candidates - remaining).iter().collect();
+                branch.set(single, true, causes, queue)?;
+            }
+        }
+
+        Some(())
+    }
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::sudoku::strats::test_util::{basic_board, candidates};
+
+    #[test]
+    fn cell_forcing_chain() {
+        let board = basic_board(
+            "...2.5.8.72.83............71.79...2.4.2.....8...5..3...8...........1.9.4.6.3.....",
+        );
+
+        let result = (CELL_FORCING_CHAIN.find)(&board);
+
+        assert_eq!(result.eliminations, candidates("C3#3"));
+
+        // a bivalue cell, with a branch from each of its notes
+        assert_eq!(result.branches.len(), 2);
+
+        let premises: Set<Candidate> = result.branches.iter().map(|b| b.premise).collect();
+        assert_eq!(premises, result.highlights);
+
+        for branch in &result.branches {
+            assert!(branch.value && !branch.contradiction);
+            assert!(branch.path.contains(branch.premise));
+        }
+    }
+
+    #[test]
+    fn forcing_chain_with_contradiction() {
+        let board = basic_board(
+            "....1..7...9...4..5...9.........31........597.27...8...761..92..347....8..2....4.",
+        );
+
+        let result = (CELL_FORCING_CHAIN.find)(&board);
+
+        // A2 can't be 6, so everything following from A2 = 4 holds
+        assert_eq!(result.solutions, candidates("A2#4 A6#6 C4#4"));
+        assert_eq!(result.eliminations, candidates("C2#4 E4#4 E6#6 I6#6"));
+
+        let branches = result
+            .branches
+            .iter()
+            .map(|b| (b.premise, b.value, b.contradiction))
+            .collect_vec();
+
+        let [a2_4, a2_6] = ["A2#4", "A2#6"].map(|c| candidates(c).iter().next().unwrap());
+        assert_eq!(branches, vec![(a2_4, true, false), (a2_6, true, true)]);
+
+        // the contradiction is reached from its own premise
+        assert!(result.branches[1].path.contains(a2_6));
+    }
+
+    #[test]
+    fn unit_forcing_chain() {
+        let board = basic_board(
+            "8.37......9....5.47.....2.8.....41...82693.....58.7.....8.6..........41..29......",
+        );
+
+        let result = (UNIT_FORCING_CHAIN.find)(&board);
+
+        assert_eq!(result.eliminations, candidates("F8#9"));
+        assert_eq!(result.branches.len(), 2);
+    }
+
+    #[test]
+    fn digit_forcing_chain() {
+        let board = basic_board(
+            "...2.5.8.72.83............71.79...2.4.2.....8...5..3...8...........1.9.4.6.3.....",
+        );
+
+        let result = (DIGIT_FORCING_CHAIN.find)(&board);
+
+        assert_eq!(result.eliminations, candidates("C3#3"));
+        assert_eq!(result.branches.len(), 2);
+    }
+
+    #[test]
+    fn no_forcing_chain() {
+        // a solved grid with a deadly pattern left open, which has two
+        // solutions, so nothing holds in every branch
+        let board = basic_board(
+            "8.47152.31376295482.53481.7913456872752891436648237915326984751579162384481573629",
+        );
+
+        for strategy in [CELL_FORCING_CHAIN, UNIT_FORCING_CHAIN, DIGIT_FORCING_CHAIN] {
+            assert!(
+                !(strategy.find)(&board).is_nontrivial(),
+                "{}",
+                strategy.name
+            );
+        }
+    }
+}
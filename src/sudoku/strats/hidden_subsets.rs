@@ -11,16 +11,19 @@ use super::{Strategy, StrategyResult};
 
 pub const HIDDEN_PAIR: Strategy = Strategy {
     name: "Hidden Pair",
+    difficulty: 70,
     find: find_hidden_subset::<2>,
 };
 
 pub const HIDDEN_TRIPLE: Strategy = Strategy {
     name: "Hidden Triple",
+    difficulty: 100,
     find: find_hidden_subset::<3>,
 };
 
 pub const HIDDEN_QUAD: Strategy = Strategy {
     name: "Hidden Quad",
+    difficulty: 150,
     find: find_hidden_subset::<4>,
 };
 
@@ -1,4 +1,9 @@
-use crate::sudoku::{pos::UnitClass, Block, Board, Digit, Line};
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Block, Board, Cell, Digit, Line},
+};
 
 use super::{Strategy, StrategyResult};
 
@@ -6,11 +11,13 @@ use super::{Strategy, StrategyResult};
 
 pub const INTERSECTION_POINTING: Strategy = Strategy {
     name: "Intersection Pointing",
+    difficulty: 50,
     find: find_intersection::<Block, Line>,
 };
 
 pub const INTERSECTION_CLAIMING: Strategy = Strategy {
     name: "Intersection Claiming",
+    difficulty: 50,
     find: find_intersection::<Line, Block>,
 };
 
@@ -51,3 +58,11 @@ fn find_intersection<Base: UnitClass, Cover: UnitClass>(board: &Board) -> Strate
 
     StrategyResult::default()
 }
+
+/// every block and line which intersect, along with the three cells they share.
+pub(super) fn iter_intersections() -> impl Iterator<Item = (Block, Line, Set<Cell>)> {
+    Block::iter_all()
+        .cartesian_product(Line::iter_all())
+        .map(|(block, line)| (block, line, block.cells_set() & line.cells_set()))
+        .filter(|(_, _, cells)| cells.is_nonempty())
+}
@@ -1,18 +1,24 @@
+use std::hash::Hash;
+
+use itertools::Itertools;
 use multimap::MultiMap;
 
 use crate::{
-    sudoku::{pos::UnitClass, Block, Board, Candidate, Col, Digit, Row},
+    bitset::Set,
+    sudoku::{pos::UnitClass, Block, Board, Candidate, Cell, Col, Digit, Row, Unit},
     util::{IterArrayCombinations, TryIntoArray},
 };
 
+use super::{als::Als, intersections::iter_intersections};
+
 // =============================================================================
 
-pub struct LinkGraph {
-    neighbors: MultiMap<Candidate, Candidate>,
+pub struct LinkGraph<V = Candidate> {
+    neighbors: MultiMap<V, V>,
 }
 
-impl LinkGraph {
-    pub fn new<L: LinkClass>(board: &Board) -> Self {
+impl<V: Vertex> LinkGraph<V> {
+    pub fn new<L: LinkClass<Vertex = V>>(board: &Board) -> Self {
         let mut neighbors = MultiMap::new();
 
         L::add_links(board, &mut neighbors);
@@ -20,8 +26,8 @@ impl LinkGraph {
         Self { neighbors }
     }
 
-    pub fn neighbors(&self, candidate: &Candidate) -> Vec<Candidate> {
-        let vec = self.neighbors.get_vec(candidate);
+    pub fn neighbors(&self, vertex: &V) -> Vec<V> {
+        let vec = self.neighbors.get_vec(vertex);
 
         match vec {
             Some(vec) => vec.clone(),
@@ -32,13 +38,93 @@ impl LinkGraph {
 
 // =============================================================================
 
+/// a vertex of a [`LinkGraph`], made up of candidates of which at least one is
+/// true when the vertex is true, and none when it is false.
+pub trait Vertex: Copy + Eq + Hash {
+    /// every vertex on the board, in a fixed order.
+    fn all(board: &Board) -> Vec<Self>;
+
+    fn candidates(self) -> Set<Candidate>;
+}
+
+impl Vertex for Candidate {
+    fn all(board: &Board) -> Vec<Self> {
+        board
+            .iter_unsolved()
+            .flat_map(|cell| board.get_notes(&cell).unwrap().map(|d| (cell, d).into()))
+            .collect()
+    }
+
+    fn candidates(self) -> Set<Candidate> {
+        Set::singleton(self)
+    }
+}
+
+/// candidates of one digit, either a single candidate, a group of two or three
+/// within the intersection of a block and a line, or the candidates of the
+/// digit in an almost locked set.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Node {
+    pub digit: Digit,
+    pub cells: Set<Cell>,
+}
+
+impl From<Candidate> for Node {
+    fn from(candidate: Candidate) -> Self {
+        Node {
+            digit: candidate.digit(),
+            cells: Set::singleton(candidate.cell()),
+        }
+    }
+}
+
+impl Vertex for Node {
+    fn all(board: &Board) -> Vec<Self> {
+        let candidates = <Candidate as Vertex>::all(board)
+            .into_iter()
+            .map(Node::from);
+
+        candidates.chain(all_groups(board)).collect()
+    }
+
+    fn candidates(self) -> Set<Candidate> {
+        self.cells.map(|cell| (cell, self.digit).into())
+    }
+}
+
+/// groups of `x` in every block and line intersection with more than one
+/// candidate.
+fn groups(board: &Board, x: Digit) -> Vec<Node> {
+    let x_cells = board.cells_with_note(x);
+
+    iter_intersections()
+        .map(|(_, _, cells)| cells & x_cells)
+        .filter(|cells| cells.len() >= 2)
+        .map(|cells| Node { digit: x, cells })
+        .collect()
+}
+
+/// nodes for the candidates of each digit in an almost locked set.
+fn als_nodes(board: &Board, als: Als) -> impl Iterator<Item = Node> + Clone + '_ {
+    als.digits.iter().map(move |digit| Node {
+        digit,
+        cells: als.cells_with(board, digit),
+    })
+}
+
+// =============================================================================
+
 pub trait LinkClass {
-    fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>);
+    type Vertex;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Self::Vertex, Self::Vertex>);
 }
 
 pub struct Bivalue;
 
 impl LinkClass for Bivalue {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         for cell in board.iter_unsolved() {
             let notes = board.get_notes(&cell).unwrap();
@@ -75,6 +161,8 @@ fn add_links_bilocal<U: UnitClass>(board: &Board, neighbors: &mut MultiMap<Candi
 pub struct BilocalRow;
 
 impl LinkClass for BilocalRow {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_bilocal::<Row>(board, neighbors);
     }
@@ -83,6 +171,8 @@ impl LinkClass for BilocalRow {
 pub struct BilocalCol;
 
 impl LinkClass for BilocalCol {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_bilocal::<Col>(board, neighbors);
     }
@@ -91,6 +181,8 @@ impl LinkClass for BilocalCol {
 pub struct BilocalBlock;
 
 impl LinkClass for BilocalBlock {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_bilocal::<Block>(board, neighbors);
     }
@@ -99,6 +191,8 @@ impl LinkClass for BilocalBlock {
 pub struct Bilocal;
 
 impl LinkClass for Bilocal {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         BilocalRow::add_links(board, neighbors);
         BilocalCol::add_links(board, neighbors);
@@ -109,6 +203,8 @@ impl LinkClass for Bilocal {
 pub struct Strong;
 
 impl LinkClass for Strong {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         Bivalue::add_links(board, neighbors);
         Bilocal::add_links(board, neighbors);
@@ -118,6 +214,8 @@ impl LinkClass for Strong {
 pub struct WeakCell;
 
 impl LinkClass for WeakCell {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         for cell in board.iter_unsolved() {
             let notes = board.get_notes(&cell).unwrap();
@@ -157,6 +255,8 @@ fn add_links_weak_local<U: UnitClass>(
 pub struct WeakRow;
 
 impl LinkClass for WeakRow {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_weak_local::<Row>(board, neighbors);
     }
@@ -165,6 +265,8 @@ impl LinkClass for WeakRow {
 pub struct WeakCol;
 
 impl LinkClass for WeakCol {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_weak_local::<Col>(board, neighbors);
     }
@@ -173,6 +275,8 @@ impl LinkClass for WeakCol {
 pub struct WeakBlock;
 
 impl LinkClass for WeakBlock {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         add_links_weak_local::<Block>(board, neighbors);
     }
@@ -181,6 +285,8 @@ impl LinkClass for WeakBlock {
 pub struct WeakUnit;
 
 impl LinkClass for WeakUnit {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         WeakRow::add_links(board, neighbors);
         WeakCol::add_links(board, neighbors);
@@ -191,8 +297,180 @@ impl LinkClass for WeakUnit {
 pub struct Weak;
 
 impl LinkClass for Weak {
+    type Vertex = Candidate;
+
     fn add_links(board: &Board, neighbors: &mut MultiMap<Candidate, Candidate>) {
         WeakCell::add_links(board, neighbors);
         WeakUnit::add_links(board, neighbors);
     }
 }
+
+// grouped ---------------------------------------------------------------------
+
+/// adds the links from a candidate link class to a graph of nodes.
+fn add_candidate_links<L: LinkClass<Vertex = Candidate>>(
+    board: &Board,
+    neighbors: &mut MultiMap<Node, Node>,
+) {
+    let mut candidate_neighbors = MultiMap::new();
+
+    L::add_links(board, &mut candidate_neighbors);
+
+    // in board order rather than the map's, so searches are deterministic
+    for a in <Candidate as Vertex>::all(board) {
+        for &b in candidate_neighbors.get_vec(&a).into_iter().flatten() {
+            neighbors.insert(a.into(), b.into());
+        }
+    }
+}
+
+/// adds links between disjoint nodes of the same digit within a unit, where
+/// `is_link` decides from the cells of both nodes and the unit's cells with the
+/// digit whether they are linked. besides single candidates, `nodes` are used
+/// where they lie within the unit.
+fn add_links_grouped(
+    board: &Board,
+    neighbors: &mut MultiMap<Node, Node>,
+    nodes: &[Node],
+    is_link: fn(Set<Cell>, Set<Cell>, Set<Cell>) -> bool,
+) {
+    for x in Digit::list() {
+        let x_cells = board.cells_with_note(x);
+
+        for unit in Unit::iter_all() {
+            let x_unit_cells = unit.cells_set() & x_cells;
+
+            let singles = x_unit_cells.iter().map(|cell| Node {
+                digit: x,
+                cells: Set::singleton(cell),
+            });
+
+            let unit_nodes = nodes
+                .iter()
+                .copied()
+                .filter(|node| node.digit == x && node.cells.is_subset(&x_unit_cells));
+
+            for (a, b) in singles.chain(unit_nodes).tuple_combinations() {
+                if (a.cells & b.cells).is_nonempty() || !is_link(a.cells, b.cells, x_unit_cells) {
+                    continue;
+                }
+
+                neighbors.insert(a, b);
+                neighbors.insert(b, a);
+            }
+        }
+    }
+}
+
+/// groups of every digit.
+fn all_groups(board: &Board) -> Vec<Node> {
+    Digit::list().flat_map(|x| groups(board, x)).collect()
+}
+
+/// strong links between nodes which together hold every candidate of their
+/// digit in a unit.
+pub struct GroupedBilocal;
+
+impl LinkClass for GroupedBilocal {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        add_links_grouped(board, neighbors, &all_groups(board), |a, b, unit| {
+            (a | b) == unit
+        });
+    }
+}
+
+/// weak links between any two nodes of the same digit within a unit.
+pub struct GroupedWeakUnit;
+
+impl LinkClass for GroupedWeakUnit {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        add_links_grouped(board, neighbors, &all_groups(board), |_, _, _| true);
+    }
+}
+
+pub struct GroupedStrong;
+
+impl LinkClass for GroupedStrong {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        add_candidate_links::<Bivalue>(board, neighbors);
+        GroupedBilocal::add_links(board, neighbors);
+    }
+}
+
+pub struct GroupedWeak;
+
+impl LinkClass for GroupedWeak {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        add_candidate_links::<WeakCell>(board, neighbors);
+        GroupedWeakUnit::add_links(board, neighbors);
+    }
+}
+
+// almost locked sets ----------------------------------------------------------
+
+/// strong links between the nodes of two digits in an almost locked set. if
+/// the set has neither, it is left with too few digits for its cells.
+pub struct AlsInternal;
+
+impl LinkClass for AlsInternal {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        for als in Als::find_all(board) {
+            for (a, b) in als_nodes(board, als).tuple_combinations() {
+                neighbors.insert(a, b);
+                neighbors.insert(b, a);
+            }
+        }
+    }
+}
+
+/// weak links between any two nodes of the same digit within a unit, including
+/// the nodes of almost locked sets.
+pub struct AlsWeakUnit;
+
+impl LinkClass for AlsWeakUnit {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        let nodes = Als::find_all(board)
+            .into_iter()
+            .flat_map(|als| als_nodes(board, als))
+            .filter(|node| node.cells.len() >= 2)
+            .chain(all_groups(board))
+            .unique()
+            .collect_vec();
+
+        add_links_grouped(board, neighbors, &nodes, |_, _, _| true);
+    }
+}
+
+pub struct AlsStrong;
+
+impl LinkClass for AlsStrong {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        GroupedStrong::add_links(board, neighbors);
+        AlsInternal::add_links(board, neighbors);
+    }
+}
+
+pub struct AlsWeak;
+
+impl LinkClass for AlsWeak {
+    type Vertex = Node;
+
+    fn add_links(board: &Board, neighbors: &mut MultiMap<Node, Node>) {
+        add_candidate_links::<WeakCell>(board, neighbors);
+        AlsWeakUnit::add_links(board, neighbors);
+    }
+}
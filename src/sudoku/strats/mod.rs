@@ -25,15 +25,46 @@ mod intersections;
 use intersections::{INTERSECTION_CLAIMING, INTERSECTION_POINTING};
 
 mod fish;
-use fish::{JELLYFISH, SWORDFISH, X_WING};
+use fish::{
+    FINNED_JELLYFISH, FINNED_SWORDFISH, FINNED_X_WING, FRANKEN_FISH, JELLYFISH, MUTANT_FISH,
+    SASHIMI_JELLYFISH, SASHIMI_SWORDFISH, SASHIMI_X_WING, SWORDFISH, X_WING,
+};
+
+mod single_digit;
+use single_digit::{EMPTY_RECTANGLE, SKYSCRAPER, TURBOT_FISH, TWO_STRING_KITE};
+
+mod coloring;
+use coloring::{MEDUSA_3D, MULTI_COLORS, SIMPLE_COLORS};
+
+mod wings;
+use wings::{WXYZ_WING, W_WING, XYZ_WING, XY_WING};
+
+mod sue_de_coq;
+use sue_de_coq::SUE_DE_COQ;
+
+mod uniqueness;
+use uniqueness::{
+    HIDDEN_RECTANGLE, UNIQUE_RECTANGLE_1, UNIQUE_RECTANGLE_2, UNIQUE_RECTANGLE_3,
+    UNIQUE_RECTANGLE_4, UNIQUE_RECTANGLE_5, UNIQUE_RECTANGLE_6,
+};
 
 mod bug;
-use bug::BUG_PLUS_1;
+use bug::{BUG_PLUS_1, BUG_TYPE_2, BUG_TYPE_3, BUG_TYPE_4};
+
+mod als;
+use als::{ALS_XY_WING, ALS_XZ, DEATH_BLOSSOM};
+
+mod forcing;
+use forcing::{CELL_FORCING_CHAIN, DIGIT_FORCING_CHAIN, UNIT_FORCING_CHAIN};
+
+mod trial;
+pub use trial::find_by_trial;
+use trial::NISHIO;
 
 mod chains;
-use chains::X_CHAIN_SIMPLE;
+use chains::{REMOTE_PAIRS, X_CHAIN_SIMPLE};
 
-use self::chains::{AIC, XY_CHAIN, X_CHAIN};
+use self::chains::{AIC, ALS_AIC, GROUPED_AIC, GROUPED_X_CHAIN, XY_CHAIN, X_CHAIN};
 
 // -----------------------------------------------------------------------------
 
@@ -54,34 +85,187 @@ pub const STRATEGY_LIST: &[Strategy] = &[
     X_WING,
     SWORDFISH,
     JELLYFISH,
+    SKYSCRAPER,
+    TWO_STRING_KITE,
+    TURBOT_FISH,
+    EMPTY_RECTANGLE,
+    FINNED_X_WING,
+    SASHIMI_X_WING,
+    SIMPLE_COLORS,
+    MULTI_COLORS,
+    REMOTE_PAIRS,
+    W_WING,
+    XY_WING,
+    XYZ_WING,
+    WXYZ_WING,
+    FINNED_SWORDFISH,
+    SASHIMI_SWORDFISH,
+    FINNED_JELLYFISH,
+    SASHIMI_JELLYFISH,
+    SUE_DE_COQ,
+    UNIQUE_RECTANGLE_1,
+    UNIQUE_RECTANGLE_2,
+    UNIQUE_RECTANGLE_3,
+    UNIQUE_RECTANGLE_4,
+    UNIQUE_RECTANGLE_5,
+    UNIQUE_RECTANGLE_6,
+    HIDDEN_RECTANGLE,
     BUG_PLUS_1,
+    BUG_TYPE_2,
+    BUG_TYPE_3,
+    BUG_TYPE_4,
     X_CHAIN_SIMPLE,
     X_CHAIN,
     XY_CHAIN,
     AIC,
+    GROUPED_X_CHAIN,
+    GROUPED_AIC,
+    MEDUSA_3D,
+    ALS_XZ,
+    ALS_XY_WING,
+    DEATH_BLOSSOM,
+    ALS_AIC,
+    FRANKEN_FISH,
+    MUTANT_FISH,
+    CELL_FORCING_CHAIN,
+    UNIT_FORCING_CHAIN,
+    DIGIT_FORCING_CHAIN,
+    NISHIO,
 ];
 
-/// checklist:
-/// - skyscraper,
-/// - kite,
-/// - turbotFish,
-/// - xyWing,
-/// - xyzWing,
-/// - wWing,
-/// - ur1,
-/// - ur2,
-/// - ur4,
-/// - ur5,
-/// - hiddenRectangle,
+/// named subsets of the strategy list, for solving with some techniques
+/// forbidden
+pub const PROFILE_LIST: &[Profile] = &[
+    Profile {
+        name: "default",
+        strategies: STRATEGY_LIST,
+    },
+    Profile {
+        name: "singles-only",
+        strategies: &[REVISE_NOTES, FULL_HOUSE, NAKED_SINGLE, HIDDEN_SINGLE],
+    },
+    Profile {
+        name: "basic",
+        strategies: &[
+            REVISE_NOTES,
+            FULL_HOUSE,
+            NAKED_SINGLE,
+            HIDDEN_SINGLE,
+            NAKED_PAIR,
+            HIDDEN_PAIR,
+            INTERSECTION_POINTING,
+            INTERSECTION_CLAIMING,
+            NAKED_TRIPLE,
+            HIDDEN_TRIPLE,
+            NAKED_QUAD,
+            HIDDEN_QUAD,
+        ],
+    },
+    Profile {
+        name: "no-chains",
+        strategies: &[
+            REVISE_NOTES,
+            FULL_HOUSE,
+            NAKED_SINGLE,
+            HIDDEN_SINGLE,
+            NAKED_PAIR,
+            HIDDEN_PAIR,
+            INTERSECTION_POINTING,
+            INTERSECTION_CLAIMING,
+            NAKED_TRIPLE,
+            HIDDEN_TRIPLE,
+            NAKED_QUAD,
+            HIDDEN_QUAD,
+            X_WING,
+            SWORDFISH,
+            JELLYFISH,
+            SKYSCRAPER,
+            TWO_STRING_KITE,
+            TURBOT_FISH,
+            EMPTY_RECTANGLE,
+            FINNED_X_WING,
+            SASHIMI_X_WING,
+            SIMPLE_COLORS,
+            MULTI_COLORS,
+            W_WING,
+            XY_WING,
+            XYZ_WING,
+            WXYZ_WING,
+            FINNED_SWORDFISH,
+            SASHIMI_SWORDFISH,
+            FINNED_JELLYFISH,
+            SASHIMI_JELLYFISH,
+            SUE_DE_COQ,
+            UNIQUE_RECTANGLE_1,
+            UNIQUE_RECTANGLE_2,
+            UNIQUE_RECTANGLE_3,
+            UNIQUE_RECTANGLE_4,
+            UNIQUE_RECTANGLE_5,
+            UNIQUE_RECTANGLE_6,
+            HIDDEN_RECTANGLE,
+            BUG_PLUS_1,
+            BUG_TYPE_2,
+            BUG_TYPE_3,
+            BUG_TYPE_4,
+            MEDUSA_3D,
+            ALS_XZ,
+            ALS_XY_WING,
+            DEATH_BLOSSOM,
+            FRANKEN_FISH,
+            MUTANT_FISH,
+        ],
+    },
+];
+
+/// strategies which rely on the puzzle having a unique solution, and are only
+/// used by a [`Solver`](crate::sudoku::Solver) told to assume one
+pub const UNIQUENESS_LIST: &[Strategy] = &[
+    UNIQUE_RECTANGLE_1,
+    UNIQUE_RECTANGLE_2,
+    UNIQUE_RECTANGLE_3,
+    UNIQUE_RECTANGLE_4,
+    UNIQUE_RECTANGLE_5,
+    UNIQUE_RECTANGLE_6,
+    HIDDEN_RECTANGLE,
+    BUG_PLUS_1,
+    BUG_TYPE_2,
+    BUG_TYPE_3,
+    BUG_TYPE_4,
+];
+
+/// strategies which guess rather than deduce, and are only used by a
+/// [`Solver`](crate::sudoku::Solver) which allows them
+pub const TRIAL_LIST: &[Strategy] = &[NISHIO];
 
 // strategy --------------------------------------------------------------------
 
+/// a named solving technique.
+///
+/// `find` searches a board for an instance of the technique and returns the
+/// deductions it yields, or a trivial result if there are none.
+///
+/// `difficulty` is the score of a single use of the technique, on the scale
+/// used by HoDoKu, and is the basis of puzzle ratings.
 #[derive(Clone, Copy)]
 pub struct Strategy {
     pub name: &'static str,
+    pub difficulty: u32,
     pub find: fn(&Board) -> StrategyResult,
 }
 
+impl Strategy {
+    /// returns `true` if the strategy is only sound for puzzles with a unique
+    /// solution.
+    pub fn assumes_uniqueness(&self) -> bool {
+        UNIQUENESS_LIST.contains(self)
+    }
+
+    /// returns `true` if the strategy works by trial and error.
+    pub fn is_trial_and_error(&self) -> bool {
+        TRIAL_LIST.contains(self)
+    }
+}
+
 impl PartialEq for Strategy {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
@@ -94,18 +278,66 @@ impl std::fmt::Debug for Strategy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StrategyObject")
             .field("name", &self.name)
+            .field("difficulty", &self.difficulty)
             .finish()
     }
 }
 
+// profile ---------------------------------------------------------------------
+
+/// a named set of strategies to enable, such as "singles-only".
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Profile {
+    pub name: &'static str,
+    pub strategies: &'static [Strategy],
+}
+
+impl Profile {
+    /// finds the profile in [`PROFILE_LIST`] with the given name.
+    pub fn from_name(name: &str) -> Option<Profile> {
+        PROFILE_LIST
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// returns `true` if the profile enables `strategy`.
+    pub fn contains(&self, strategy: &Strategy) -> bool {
+        self.strategies.contains(strategy)
+    }
+}
+
 // result ----------------------------------------------------------------------
 
+/// deductions found by a strategy, along with candidates to highlight when
+/// displaying them.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct StrategyResult {
+    /// candidates which must be true
     pub solutions: Set<Candidate>,
+    /// candidates which must be false
     pub eliminations: Set<Candidate>,
+    /// primary candidates involved in the deduction
     pub highlights: Set<Candidate>,
+    /// secondary candidates involved in the deduction
     pub highlights2: Set<Candidate>,
+    /// each case tried by a deduction which tries several, such as a forcing
+    /// chain
+    pub branches: Vec<StrategyBranch>,
+}
+
+/// one case of a deduction which tries several, starting from an assumption
+/// that `premise` is true or false.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct StrategyBranch {
+    pub premise: Candidate,
+    /// whether `premise` was assumed to be true
+    pub value: bool,
+    /// candidates the deductions of the branch were reached through, including
+    /// the premise
+    pub path: Set<Candidate>,
+    /// whether the branch leads to a contradiction, so its premise can't hold
+    pub contradiction: bool,
 }
 
 impl Default for StrategyResult {
@@ -115,12 +347,86 @@ impl Default for StrategyResult {
             eliminations: Set::new(),
             highlights: Set::new(),
             highlights2: Set::new(),
+            branches: Vec::new(),
         }
     }
 }
 
 impl StrategyResult {
+    /// returns `true` if the result has any solutions or eliminations.
     pub fn is_nontrivial(&self) -> bool {
         !self.solutions.is_empty() || !self.eliminations.is_empty()
     }
 }
+
+// tests -----------------------------------------------------------------------
+
+/// helpers for testing strategies on boards from real puzzles.
+#[cfg(test)]
+mod test_util {
+    use super::*;
+    use crate::sudoku::Solver;
+
+    /// the board left after solving `puzzle` as far as the basic profile goes.
+    pub(super) fn basic_board(puzzle: &str) -> Board {
+        let basic = Profile::from_name("basic").unwrap();
+        let mut solver = Solver::from_profile(&basic);
+
+        solver.load_board_string(puzzle);
+        solver.solve().board
+    }
+
+    /// the board left after solving `puzzle` with the strategies listed before
+    /// `strategy`, leaving out those which assume uniqueness or guess, so that
+    /// `strategy` is the next one the solver would try.
+    pub(super) fn board_before(puzzle: &str, strategy: Strategy) -> Board {
+        let strategies: Vec<Strategy> = STRATEGY_LIST
+            .iter()
+            .take_while(|&&other| other != strategy)
+            .filter(|other| !other.assumes_uniqueness() && !other.is_trial_and_error())
+            .copied()
+            .collect();
+
+        let mut solver = Solver::new();
+
+        solver.set_strategies(&strategies);
+        solver.load_board_string(puzzle);
+        solver.solve().board
+    }
+
+    /// asserts that `strategy` eliminates exactly the candidates in
+    /// `eliminations` on `board`, returning its result for further checks.
+    pub(super) fn assert_eliminations(
+        strategy: Strategy,
+        board: &Board,
+        eliminations: &str,
+    ) -> StrategyResult {
+        let result = (strategy.find)(board);
+
+        assert_eq!(
+            result.eliminations,
+            candidates(eliminations),
+            "{}",
+            strategy.name
+        );
+
+        result
+    }
+
+    /// asserts that none of `strategies` finds anything on `board`.
+    pub(super) fn assert_finds_nothing(board: &Board, strategies: &[Strategy]) {
+        for strategy in strategies {
+            assert!(!(strategy.find)(board).is_nontrivial(), "{}", strategy.name);
+        }
+    }
+
+    /// parses a list of candidates such as `A1#2 B3#4`.
+    pub(super) fn candidates(list: &str) -> Set<Candidate> {
+        list.split_whitespace()
+            .map(|candidate| {
+                let (cell, digit) = candidate.split_once('#').unwrap();
+                (cell.parse().unwrap(), digit.parse().unwrap()).into()
+            })
+            .collect()
+    }
+}
@@ -11,16 +11,19 @@ use super::{Strategy, StrategyResult};
 
 pub const NAKED_PAIR: Strategy = Strategy {
     name: "Naked Pair",
+    difficulty: 60,
     find: find_naked_subset::<2>,
 };
 
 pub const NAKED_TRIPLE: Strategy = Strategy {
     name: "Naked Triple",
+    difficulty: 80,
     find: find_naked_subset::<3>,
 };
 
 pub const NAKED_QUAD: Strategy = Strategy {
     name: "Naked Quad",
+    difficulty: 120,
     find: find_naked_subset::<4>,
 };
 
@@ -31,40 +34,22 @@ fn find_naked_subset<const N: usize>(board: &Board) -> StrategyResult {
         let unsolved_cells = unit.cells_set() & board.cells_unsolved();
 
         for cell_vec in unsolved_cells.iter().combinations(N) {
-            let digit_set: Set<Digit> = cell_vec
-                .iter()
-                .map(|cell| board.get_notes(cell).unwrap())
-                .sum();
+            let cell_set: Set<Cell> = cell_vec.into_iter().collect();
+            let digit_set = subset_notes(board, cell_set);
 
             if digit_set.len() != N {
                 continue;
             }
 
-            let cell_set: Set<Cell> = cell_vec.into_iter().collect();
-
-            let eliminations: Set<Candidate> = (unsolved_cells - cell_set)
-                .iter()
-                .map(|cell| {
-                    let notes = board.get_notes(&cell).unwrap();
-
-                    (*notes & digit_set).map(|digit| (cell, digit).into())
-                })
-                .sum();
+            let eliminations = subset_candidates(board, unsolved_cells - cell_set, digit_set);
 
             if eliminations.is_empty() {
                 continue;
             }
 
-            let highlights = cell_set
-                .iter()
-                .cartesian_product(digit_set.iter())
-                .filter(|(cell, digit)| board.has_note(cell, *digit))
-                .map(|(cell, digit)| (cell, digit).into())
-                .collect();
-
             return StrategyResult {
                 eliminations,
-                highlights,
+                highlights: subset_candidates(board, cell_set, digit_set),
                 ..Default::default()
             };
         }
@@ -72,3 +57,24 @@ fn find_naked_subset<const N: usize>(board: &Board) -> StrategyResult {
 
     StrategyResult::default()
 }
+
+/// every note of the unsolved cells in `cells`.
+pub(super) fn subset_notes(board: &Board, cells: Set<Cell>) -> Set<Digit> {
+    cells.iter().filter_map(|cell| board.get_notes(&cell)).sum()
+}
+
+/// candidates of `digits` in the unsolved cells in `cells`.
+pub(super) fn subset_candidates(
+    board: &Board,
+    cells: Set<Cell>,
+    digits: Set<Digit>,
+) -> Set<Candidate> {
+    cells
+        .iter()
+        .filter_map(|cell| {
+            let notes = board.get_notes(&cell)?;
+
+            Some((*notes & digits).map(|digit| (cell, digit).into()))
+        })
+        .sum()
+}
@@ -6,6 +6,7 @@ use super::{Strategy, StrategyResult};
 
 pub const REVISE_NOTES: Strategy = Strategy {
     name: "Revise Notes",
+    difficulty: 0,
     find: |board| {
         let eliminations: Set<Candidate> = board
             .iter_solved()
@@ -0,0 +1,229 @@
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Block, Board, Candidate, Cell, Digit},
+};
+
+use super::{
+    link::{
+        Bilocal, BilocalCol, BilocalRow, LinkClass, LinkGraph, WeakBlock, WeakCol, WeakRow,
+        WeakUnit,
+    },
+    Strategy, StrategyResult,
+};
+
+// =============================================================================
+
+pub const SKYSCRAPER: Strategy = Strategy {
+    name: "Skyscraper",
+    difficulty: 130,
+    find: find_skyscraper,
+};
+
+pub const TWO_STRING_KITE: Strategy = Strategy {
+    name: "2-String Kite",
+    difficulty: 150,
+    find: find_turbot_fish::<BilocalRow, WeakBlock, BilocalCol>,
+};
+
+pub const TURBOT_FISH: Strategy = Strategy {
+    name: "Turbot Fish",
+    difficulty: 120,
+    find: find_turbot_fish::<Bilocal, WeakUnit, Bilocal>,
+};
+
+pub const EMPTY_RECTANGLE: Strategy = Strategy {
+    name: "Empty Rectangle",
+    difficulty: 120,
+    find: find_empty_rectangle,
+};
+
+// =============================================================================
+
+fn find_skyscraper(board: &Board) -> StrategyResult {
+    let result = find_turbot_fish::<BilocalRow, WeakCol, BilocalRow>(board);
+
+    if result.is_nontrivial() {
+        return result;
+    }
+
+    find_turbot_fish::<BilocalCol, WeakRow, BilocalCol>(board)
+}
+
+/// finds a chain `a = b - c = d` on a single digit, where `a = b` is a link
+/// from `First`, `b - c` is a link from `Weak` and `c = d` is a link from
+/// `Last`. one of `a` or `d` must be true, so the digit can be eliminated from
+/// any cell which sees both.
+fn find_turbot_fish<First, Weak, Last>(board: &Board) -> StrategyResult
+where
+    First: LinkClass<Vertex = Candidate>,
+    Weak: LinkClass<Vertex = Candidate>,
+    Last: LinkClass<Vertex = Candidate>,
+{
+    let first_links = LinkGraph::new::<First>(board);
+    let weak_links = LinkGraph::new::<Weak>(board);
+    let last_links = LinkGraph::new::<Last>(board);
+
+    for x in Digit::list() {
+        let x_cells = board.cells_with_note(x);
+
+        for a in x_cells.map(|cell| Candidate::from((cell, x))) {
+            for b in first_links.neighbors(&a) {
+                for c in weak_links.neighbors(&b) {
+                    if c == a {
+                        continue;
+                    }
+
+                    for d in last_links.neighbors(&c) {
+                        if d == a || d == b {
+                            continue;
+                        }
+
+                        let elim_cells = a.cell().neighbors() & d.cell().neighbors() & x_cells;
+
+                        if elim_cells.is_empty() {
+                            continue;
+                        }
+
+                        return StrategyResult {
+                            eliminations: elim_cells.map(|cell| (cell, x).into()),
+                            highlights: Set::from([a, c].as_slice()),
+                            highlights2: Set::from([b, d].as_slice()),
+                            ..Default::default()
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// finds a block whose candidates of a digit all lie in one row and one column
+/// through it, but not in just one of them, along with a strong link in a
+/// column `p = q` where `p` is in the row, outside the block.
+///
+/// if `q` is false, `p` is true, so the block's candidates must be in the
+/// column. either way the cell in the column and `q`'s row can't be the digit.
+/// the same holds with rows and columns swapped.
+fn find_empty_rectangle(board: &Board) -> StrategyResult {
+    let row_links = LinkGraph::new::<BilocalRow>(board);
+    let col_links = LinkGraph::new::<BilocalCol>(board);
+
+    for x in Digit::list() {
+        let x_cells = board.cells_with_note(x);
+
+        for block in Block::iter_all() {
+            let block_cells = block.cells_set() & x_cells;
+
+            if block_cells.len() < 2 {
+                continue;
+            }
+
+            for hinge in block.cells_set() {
+                let row_cells = hinge.row().cells_set();
+                let col_cells = hinge.col().cells_set();
+
+                if !block_cells.is_subset(&(row_cells | col_cells))
+                    || block_cells.is_subset(&row_cells)
+                    || block_cells.is_subset(&col_cells)
+                {
+                    continue;
+                }
+
+                let arms = [
+                    (row_cells, &col_links, true),
+                    (col_cells, &row_links, false),
+                ];
+
+                for (line_cells, links, in_row) in arms {
+                    for p in (line_cells & x_cells) - block.cells_set() {
+                        let p = Candidate::from((p, x));
+
+                        for q in links.neighbors(&p) {
+                            let target = if in_row {
+                                Cell::from_row_and_col(q.cell().row(), hinge.col())
+                            } else {
+                                Cell::from_row_and_col(hinge.row(), q.cell().col())
+                            };
+
+                            if block.cells_set().contains(target) || !board.has_note(&target, x) {
+                                continue;
+                            }
+
+                            return StrategyResult {
+                                eliminations: Set::singleton((target, x).into()),
+                                highlights: block_cells.map(|cell| (cell, x).into()),
+                                highlights2: Set::from([p, q].as_slice()),
+                                ..Default::default()
+                            };
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, board_before,
+    };
+
+    #[test]
+    fn skyscraper() {
+        let board = basic_board(
+            ".2..5.3....8........5..6.4....8..2..1..52....2....17.38.3.4.....57...4.....93.56.",
+        );
+
+        assert_eliminations(SKYSCRAPER, &board, "G4#2 H9#2");
+    }
+
+    #[test]
+    fn two_string_kite() {
+        let board = basic_board(
+            ".4....3..6..8.1..7.1..43.8.89..6..7......8.3...7......98...214...1..5....3.......",
+        );
+
+        assert_finds_nothing(&board, &[SKYSCRAPER]);
+
+        assert_eliminations(TWO_STRING_KITE, &board, "F4#5");
+    }
+
+    #[test]
+    fn turbot_fish() {
+        let board = basic_board(
+            ".7.3...8...2...1...68..1.2.3.5.97........2....2....93...6.7.8.2.....6.4....8...5.",
+        );
+
+        // neither the Skyscraper nor the 2-String Kite shape applies here
+        assert_finds_nothing(&board, &[SKYSCRAPER, TWO_STRING_KITE]);
+
+        assert_eliminations(TURBOT_FISH, &board, "B6#9");
+    }
+
+    #[test]
+    fn empty_rectangle() {
+        let board = board_before(
+            "..1......54..8...3..2..3.6..9....6.....91...87..2.......7.4..351..5..84......7.1.",
+            EMPTY_RECTANGLE,
+        );
+
+        assert_eliminations(EMPTY_RECTANGLE, &board, "H3#6");
+    }
+
+    #[test]
+    fn no_empty_rectangle() {
+        let board = basic_board(
+            "..........6...1....518........7..2.67...6.1.31....85..3279....49...........4..3..",
+        );
+
+        assert_finds_nothing(&board, &[EMPTY_RECTANGLE]);
+    }
+}
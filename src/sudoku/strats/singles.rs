@@ -10,6 +10,7 @@ use super::{Strategy, StrategyResult};
 
 pub const FULL_HOUSE: Strategy = Strategy {
     name: "Full House",
+    difficulty: 4,
     find: |board| {
         let mut solutions = Set::new();
 
@@ -41,6 +42,7 @@ pub const FULL_HOUSE: Strategy = Strategy {
 
 pub const NAKED_SINGLE: Strategy = Strategy {
     name: "Naked Single",
+    difficulty: 4,
     find: |board| {
         let mut solutions = Set::new();
 
@@ -61,6 +63,7 @@ pub const NAKED_SINGLE: Strategy = Strategy {
 
 pub const HIDDEN_SINGLE: Strategy = Strategy {
     name: "Hidden Single",
+    difficulty: 14,
     find: |board| {
         let mut solutions = Set::new();
 
@@ -0,0 +1,155 @@
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Board, Cell, Digit},
+};
+
+use super::{
+    intersections::iter_intersections,
+    naked_subsets::{subset_candidates, subset_notes},
+    Strategy, StrategyResult,
+};
+
+// =============================================================================
+
+pub const SUE_DE_COQ: Strategy = Strategy {
+    name: "Sue de Coq",
+    difficulty: 250,
+    find: find_sue_de_coq,
+};
+
+// =============================================================================
+
+/// finds two or three cells in the intersection of a block and a line with at
+/// least two more digits than cells, along with a set of other cells in the
+/// line and a set of other cells in the block, sharing no digits with each
+/// other, such that all three sets have as many digits between them as cells.
+///
+/// a digit from the line set can only be in the intersection or the line set,
+/// which all see each other, and likewise for the block set, so no digit
+/// appears twice. since there are as many digits as cells, every digit appears
+/// once: digits of the line set can be eliminated from the rest of the line,
+/// digits of the block set from the rest of the block, and digits only in the
+/// intersection from both.
+///
+/// the intersection is highlighted along with the line set, and the block set
+/// in the second color.
+fn find_sue_de_coq(board: &Board) -> StrategyResult {
+    let unsolved = board.cells_unsolved();
+
+    for (block, line, cells) in iter_intersections() {
+        let core_cells = cells & unsolved;
+        let line_cells = line.cells_set() & unsolved;
+        let block_cells = block.cells_set() & unsolved;
+
+        let line_subsets = subsets(board, line_cells - core_cells);
+        let block_subsets = subsets(board, block_cells - core_cells);
+
+        for size in 2..=core_cells.len() {
+            for core in core_cells.iter().combinations(size) {
+                let core: Set<Cell> = core.into_iter().collect();
+                let core_digits = subset_notes(board, core);
+
+                if core_digits.len() < size + 2 {
+                    continue;
+                }
+
+                let supports = |subsets: &[(Set<Cell>, Set<Digit>)]| {
+                    subsets
+                        .iter()
+                        .copied()
+                        .filter(|(_, digits)| (*digits & core_digits).is_nonempty())
+                        .collect_vec()
+                };
+
+                let line_supports = supports(&line_subsets);
+                let block_supports = supports(&block_subsets);
+
+                for &(line_set, line_digits) in &line_supports {
+                    for &(block_set, block_digits) in &block_supports {
+                        if (line_digits & block_digits).is_nonempty() {
+                            continue;
+                        }
+
+                        let digits = core_digits | line_digits | block_digits;
+
+                        if digits.len() != size + line_set.len() + block_set.len() {
+                            continue;
+                        }
+
+                        let eliminations = subset_candidates(
+                            board,
+                            line_cells - core - line_set,
+                            line_digits | (core_digits - block_digits),
+                        ) | subset_candidates(
+                            board,
+                            block_cells - core - block_set,
+                            block_digits | (core_digits - line_digits),
+                        );
+
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+
+                        return StrategyResult {
+                            eliminations,
+                            highlights: subset_candidates(board, core | line_set, digits),
+                            highlights2: subset_candidates(board, block_set, digits),
+                            ..Default::default()
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// every nonempty subset of `cells`, along with its notes.
+fn subsets(board: &Board, cells: Set<Cell>) -> Vec<(Set<Cell>, Set<Digit>)> {
+    (1..=cells.len())
+        .flat_map(|size| cells.iter().combinations(size))
+        .map(|subset| {
+            let subset: Set<Cell> = subset.into_iter().collect();
+            (subset, subset_notes(board, subset))
+        })
+        .collect()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, candidates,
+    };
+
+    #[test]
+    fn sue_de_coq() {
+        let board = basic_board(
+            "....416..15........76...5......8.....23...7.16.71.......93....2..56..3......2...8",
+        );
+
+        // G1 and G2 in row G and block 7, with G5 and G8 in the row and H2
+        // and I3 in the block
+        let result = assert_eliminations(SUE_DE_COQ, &board, "G6#5 I2#1 I2#4");
+
+        assert_eq!(
+            result.highlights,
+            candidates("G1#7 G1#8 G2#1 G2#4 G2#6 G2#8 G5#5 G5#7 G8#5 G8#6")
+        );
+        assert_eq!(result.highlights2, candidates("H2#4 H2#8 I3#1 I3#4"));
+    }
+
+    #[test]
+    fn no_sue_de_coq() {
+        let board = basic_board(
+            ".......598.2.3....19..8...44..7..9..6..9.4..19..3.5...5......16......2.....1....3",
+        );
+
+        assert_finds_nothing(&board, &[SUE_DE_COQ]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    bitset::Set,
+    sudoku::{pos::UnitClass, Board, Candidate, Digit, Unit},
+};
+
+use super::{link::Vertex, Profile, Strategy, StrategyResult};
+
+// =============================================================================
+
+/// trial and error using the strategies of the "basic" [`Profile`], so each
+/// trial stays quick. the [`Solver`](crate::sudoku::Solver) doesn't use this,
+/// but calls [`find_by_trial`] with its own enabled strategies instead.
+pub const NISHIO: Strategy = Strategy {
+    name: "Trial and Error (Nishio)",
+    difficulty: 10000,
+    find: |board| find_by_trial(board, Profile::from_name("basic").unwrap().strategies),
+};
+
+// =============================================================================
+
+/// tries each note of every bivalue cell in turn, placing it on a copy of the
+/// board and solving on from there with `strategies`. a note which leads to a
+/// contradiction is eliminated. if no bivalue cell gives anything, every other
+/// note of every unsolved cell is tried the same way.
+///
+/// this is guessing rather than a logical technique, so it is only tried when
+/// everything else fails, and only once allowed with
+/// [`Solver::set_allow_trial`](crate::sudoku::Solver::set_allow_trial).
+/// trial strategies in `strategies` are skipped, so trials don't nest.
+pub fn find_by_trial(board: &Board, strategies: &[Strategy]) -> StrategyResult {
+    let strategies: Vec<Strategy> = strategies
+        .iter()
+        .filter(|strategy| !strategy.is_trial_and_error())
+        .copied()
+        .collect();
+
+    let (bivalue, rest): (Vec<Candidate>, Vec<Candidate>) = Candidate::all(board)
+        .into_iter()
+        .partition(|candidate| board.count_notes(&candidate.cell()) == 2);
+
+    for candidate in bivalue.into_iter().chain(rest) {
+        let mut trial = board.clone();
+        place(&mut trial, candidate);
+
+        if !leads_to_contradiction(trial, &strategies) {
+            continue;
+        }
+
+        return StrategyResult {
+            eliminations: Set::singleton(candidate),
+            highlights: Set::singleton(candidate),
+            ..Default::default()
+        };
+    }
+
+    StrategyResult::default()
+}
+
+/// applies `strategies` to `board` until it is solved, no strategy finds
+/// anything, or it reaches a contradiction, returning `true` in the last case.
+fn leads_to_contradiction(mut board: Board, strategies: &[Strategy]) -> bool {
+    loop {
+        if has_contradiction(&board) {
+            return true;
+        }
+
+        if board.is_solved() {
+            return false;
+        }
+
+        let Some(result) = strategies
+            .iter()
+            .map(|strategy| (strategy.find)(&board))
+            .find(StrategyResult::is_nontrivial)
+        else {
+            return false;
+        };
+
+        let before = board.clone();
+
+        for solution in result.solutions {
+            let (cell, digit) = solution.as_tuple();
+
+            match board.get_digit(&cell) {
+                Some(placed) if placed != digit => return true,
+                Some(_) => {}
+                None => place(&mut board, solution),
+            }
+        }
+
+        for elimination in result.eliminations {
+            if board.has_note(&elimination.cell(), elimination.digit()) {
+                board.input_elimination(elimination);
+            }
+        }
+
+        if board == before {
+            return false;
+        }
+    }
+}
+
+/// places `candidate` and removes its digit from the notes of every cell
+/// which sees it.
+fn place(board: &mut Board, candidate: Candidate) {
+    let (cell, digit) = candidate.as_tuple();
+
+    board.input_solution(candidate);
+
+    for neighbor in cell.neighbors() {
+        if board.has_note(&neighbor, digit) {
+            board.input_elimination((neighbor, digit).into());
+        }
+    }
+}
+
+/// returns `true` if some unsolved cell has no notes left, some unit has a
+/// digit placed twice, or some unit has nowhere left for a digit.
+fn has_contradiction(board: &Board) -> bool {
+    if board
+        .iter_unsolved()
+        .any(|cell| board.count_notes(&cell) == 0)
+    {
+        return true;
+    }
+
+    Unit::list().any(|unit| {
+        Digit::list().any(|digit| {
+            let placed = (unit.cells_set() & board.cells_with_digit(digit)).len();
+            let noted = (unit.cells_set() & board.cells_with_note(digit)).len();
+
+            placed > 1 || placed + noted == 0
+        })
+    })
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::{
+        strats::test_util::{basic_board, candidates},
+        Profile,
+    };
+
+    #[test]
+    fn falls_back_to_every_note() {
+        let board = basic_board(
+            ".7.2.6...3..9......4...1.8.....1....4...2..13.1.5.46..1...6.7.9.26.3............5",
+        );
+        let singles = Profile::from_name("singles-only").unwrap().strategies;
+
+        // no note of a bivalue cell leads to a contradiction with singles, so
+        // the three-note cell A1 is tried
+        assert_eq!(board.count_notes(&"A1".parse().unwrap()), 3);
+        assert_eq!(
+            find_by_trial(&board, singles).eliminations,
+            candidates("A1#9")
+        );
+    }
+
+    #[test]
+    fn tries_bivalue_cells_first() {
+        let board = basic_board(
+            "........3..7.........1..459.62..3.9..9..85.7.5.37....2..6......9..8..5...3......7",
+        );
+        let basic = Profile::from_name("basic").unwrap().strategies;
+
+        // A2 = 5 also leads to a contradiction, and A2 comes before A4, but
+        // A4 is bivalue so it is tried first
+        let a2_5 = candidates("A2#5").iter().next().unwrap();
+        assert_eq!(board.count_notes(&a2_5.cell()), 3);
+        assert_eq!(board.count_notes(&"A4".parse().unwrap()), 2);
+
+        let mut trial = board.clone();
+        place(&mut trial, a2_5);
+        assert!(leads_to_contradiction(trial, basic));
+
+        assert_eq!((NISHIO.find)(&board).eliminations, candidates("A4#6"));
+    }
+}
@@ -0,0 +1,475 @@
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{Board, Candidate, Cell, Col, Digit, Row, Unit, UnitClass},
+    util::TryIntoArray,
+};
+
+use super::{Strategy, StrategyResult};
+
+// =============================================================================
+
+pub const UNIQUE_RECTANGLE_1: Strategy = Strategy {
+    name: "Unique Rectangle Type 1",
+    difficulty: 100,
+    find: |board| find_rectangle(board, ur_type_1),
+};
+
+pub const UNIQUE_RECTANGLE_2: Strategy = Strategy {
+    name: "Unique Rectangle Type 2",
+    difficulty: 100,
+    find: |board| find_rectangle(board, |board, rect| ur_type_2(board, rect, true)),
+};
+
+pub const UNIQUE_RECTANGLE_3: Strategy = Strategy {
+    name: "Unique Rectangle Type 3",
+    difficulty: 100,
+    find: |board| find_rectangle(board, ur_type_3),
+};
+
+pub const UNIQUE_RECTANGLE_4: Strategy = Strategy {
+    name: "Unique Rectangle Type 4",
+    difficulty: 100,
+    find: |board| find_rectangle(board, ur_type_4),
+};
+
+pub const UNIQUE_RECTANGLE_5: Strategy = Strategy {
+    name: "Unique Rectangle Type 5",
+    difficulty: 100,
+    find: |board| find_rectangle(board, |board, rect| ur_type_2(board, rect, false)),
+};
+
+pub const UNIQUE_RECTANGLE_6: Strategy = Strategy {
+    name: "Unique Rectangle Type 6",
+    difficulty: 100,
+    find: |board| find_rectangle(board, ur_type_6),
+};
+
+pub const HIDDEN_RECTANGLE: Strategy = Strategy {
+    name: "Hidden Rectangle",
+    difficulty: 100,
+    find: |board| find_rectangle(board, hidden_rectangle),
+};
+
+// =============================================================================
+
+/// four unsolved cells at the corners of a rectangle spanning exactly two
+/// blocks, which all have both `digits` as notes.
+///
+/// if the puzzle has a unique solution, the four cells can't be only `digits`,
+/// since the two digits could then be swapped to give a second solution. each
+/// deduction below follows from ruling out this deadly pattern.
+struct Rectangle {
+    /// corners in row-major order, so `cells[i]` is diagonal to `cells[3 - i]`
+    cells: [Cell; 4],
+    digits: [Digit; 2],
+    /// corners with only `digits` as notes
+    floor: Set<Cell>,
+    /// corners with other notes besides `digits`
+    roof: Set<Cell>,
+}
+
+impl Rectangle {
+    fn cells_set(&self) -> Set<Cell> {
+        self.cells.iter().copied().collect()
+    }
+
+    fn opposite(&self, cell: Cell) -> Cell {
+        let i = self.cells.iter().position(|&c| c == cell).unwrap();
+        self.cells[3 - i]
+    }
+
+    /// candidates of `digits` in every corner.
+    fn candidates(&self) -> Set<Candidate> {
+        self.cells
+            .iter()
+            .flat_map(|&cell| self.digits.map(|digit| (cell, digit).into()))
+            .collect()
+    }
+}
+
+/// tries `find` on every rectangle on the board, returning the first
+/// nontrivial result.
+fn find_rectangle(board: &Board, find: fn(&Board, &Rectangle) -> StrategyResult) -> StrategyResult {
+    for (r1, r2) in Row::iter_all().tuple_combinations() {
+        for (c1, c2) in Col::iter_all().tuple_combinations() {
+            let cells = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)]
+                .map(|(row, col)| Cell::from_row_and_col(row, col));
+
+            if cells.iter().map(|cell| cell.block()).unique().count() != 2 {
+                continue;
+            }
+
+            let Some(notes) = cells
+                .iter()
+                .map(|cell| board.get_notes(cell).copied())
+                .fold_options(Set::full(), |acc, notes| acc & notes)
+            else {
+                continue;
+            };
+
+            for digits in notes.iter().combinations(2) {
+                let digits: [Digit; 2] = digits.try_into().unwrap();
+                let pair: Set<Digit> = Set::from(digits.as_slice());
+
+                let floor: Set<Cell> = cells
+                    .into_iter()
+                    .filter(|cell| board.get_notes(cell) == Some(&pair))
+                    .collect();
+
+                let rect = Rectangle {
+                    cells,
+                    digits,
+                    floor,
+                    roof: cells.into_iter().collect::<Set<Cell>>() - floor,
+                };
+
+                let result = find(board, &rect);
+
+                if result.is_nontrivial() {
+                    return result;
+                }
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// units which contain every cell in `cells`.
+fn common_units(cells: Set<Cell>) -> impl Iterator<Item = Unit> {
+    Unit::list().filter(move |unit| cells.is_subset(&unit.cells_set()))
+}
+
+/// notes of `cells` other than `digits`.
+fn extra_notes(board: &Board, cells: Set<Cell>, digits: [Digit; 2]) -> Set<Digit> {
+    cells
+        .iter()
+        .map(|cell| *board.get_notes(&cell).unwrap())
+        .fold(Set::new(), |acc, notes| acc | notes)
+        - Set::from(digits.as_slice())
+}
+
+// -----------------------------------------------------------------------------
+
+/// one corner has extra notes. it can't be either digit without completing the
+/// deadly pattern, so both are eliminated from it.
+fn ur_type_1(_: &Board, rect: &Rectangle) -> StrategyResult {
+    let Ok(roof) = rect.roof.try_singleton() else {
+        return StrategyResult::default();
+    };
+
+    let eliminations: Set<Candidate> = rect.digits.iter().map(|&d| (roof, d).into()).collect();
+
+    StrategyResult {
+        eliminations,
+        highlights: rect.candidates() - eliminations,
+        ..Default::default()
+    }
+}
+
+/// two or three corners have the same single extra note. one of them must be
+/// that digit, so it is eliminated from any cell which sees all of them.
+///
+/// type 2 covers two corners sharing a unit, and type 5 covers the rest.
+fn ur_type_2(board: &Board, rect: &Rectangle, adjacent: bool) -> StrategyResult {
+    if !(2..=3).contains(&rect.roof.len()) {
+        return StrategyResult::default();
+    }
+
+    if adjacent != (rect.roof.len() == 2 && common_units(rect.roof).next().is_some()) {
+        return StrategyResult::default();
+    }
+
+    let Ok(extra) = extra_notes(board, rect.roof, rect.digits).try_singleton() else {
+        return StrategyResult::default();
+    };
+
+    let elim_cells = rect
+        .roof
+        .iter()
+        .fold(board.cells_with_note(extra), |acc, cell| {
+            acc & cell.neighbors()
+        });
+
+    StrategyResult {
+        eliminations: elim_cells.map(|cell| (cell, extra).into()),
+        highlights: rect.candidates(),
+        highlights2: rect.roof.map(|cell| (cell, extra).into()),
+        ..Default::default()
+    }
+}
+
+/// two corners sharing a unit have extra notes, so one of them must be one of
+/// those extra digits. they act as a single cell with the extra digits as
+/// notes, which can form a naked subset with other cells in the unit.
+fn ur_type_3(board: &Board, rect: &Rectangle) -> StrategyResult {
+    if rect.roof.len() != 2 {
+        return StrategyResult::default();
+    }
+
+    let extras = extra_notes(board, rect.roof, rect.digits);
+
+    for unit in common_units(rect.roof) {
+        let others = (unit.cells_set() & board.cells_unsolved()) - rect.roof;
+
+        for size in 1..others.len() {
+            for subset in others.iter().combinations(size) {
+                let digits = subset
+                    .iter()
+                    .map(|cell| *board.get_notes(cell).unwrap())
+                    .fold(extras, |acc, notes| acc | notes);
+
+                if digits.len() != size + 1 {
+                    continue;
+                }
+
+                let subset: Set<Cell> = subset.into_iter().collect();
+
+                let eliminations: Set<Candidate> = (others - subset)
+                    .iter()
+                    .flat_map(|cell| {
+                        (*board.get_notes(&cell).unwrap() & digits).map(|d| (cell, d).into())
+                    })
+                    .collect();
+
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                let subset_candidates: Set<Candidate> = (subset | rect.roof)
+                    .iter()
+                    .flat_map(|cell| {
+                        (*board.get_notes(&cell).unwrap() & digits).map(|d| (cell, d).into())
+                    })
+                    .collect();
+
+                return StrategyResult {
+                    eliminations,
+                    highlights: rect.candidates(),
+                    highlights2: subset_candidates,
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// two corners sharing a unit have extra notes, and one digit appears in that
+/// unit only in those corners. one of them must be that digit, so neither can
+/// be the other digit.
+fn ur_type_4(board: &Board, rect: &Rectangle) -> StrategyResult {
+    if rect.roof.len() != 2 {
+        return StrategyResult::default();
+    }
+
+    for [x, y] in [rect.digits, [rect.digits[1], rect.digits[0]]] {
+        for unit in common_units(rect.roof) {
+            if unit.cells_set() & board.cells_with_note(x) != rect.roof {
+                continue;
+            }
+
+            return StrategyResult {
+                eliminations: rect.roof.map(|cell| (cell, y).into()),
+                highlights: rect.candidates() - rect.roof.map(|cell| (cell, y).into()),
+                highlights2: rect.roof.map(|cell| (cell, x).into()),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// two diagonal corners have extra notes, and one digit appears in both rows or
+/// both columns only in the rectangle. if either corner with extra notes were
+/// that digit, so would the other be, leaving the other digit in the remaining
+/// two corners. so the digit is eliminated from both.
+fn ur_type_6(board: &Board, rect: &Rectangle) -> StrategyResult {
+    if rect.roof.len() != 2 || common_units(rect.roof).next().is_some() {
+        return StrategyResult::default();
+    }
+
+    let rect_cells = rect.cells_set();
+
+    for x in rect.digits {
+        let x_cells = board.cells_with_note(x);
+
+        let in_rows = rect
+            .cells
+            .iter()
+            .all(|cell| (cell.row().cells_set() & x_cells).is_subset(&rect_cells));
+
+        let in_cols = rect
+            .cells
+            .iter()
+            .all(|cell| (cell.col().cells_set() & x_cells).is_subset(&rect_cells));
+
+        if !in_rows && !in_cols {
+            continue;
+        }
+
+        return StrategyResult {
+            eliminations: rect.roof.map(|cell| (cell, x).into()),
+            highlights: rect.candidates() - rect.roof.map(|cell| (cell, x).into()),
+            ..Default::default()
+        };
+    }
+
+    StrategyResult::default()
+}
+
+/// a corner has only the two digits as notes, and one of the digits appears in
+/// the row and column of the opposite corner only in the rectangle. if the
+/// opposite corner were the other digit, the pattern would be forced, so that
+/// digit is eliminated from it.
+fn hidden_rectangle(board: &Board, rect: &Rectangle) -> StrategyResult {
+    let rect_cells = rect.cells_set();
+
+    for floor in rect.floor {
+        let opposite = rect.opposite(floor);
+
+        for [x, y] in [rect.digits, [rect.digits[1], rect.digits[0]]] {
+            let x_cells = board.cells_with_note(x);
+
+            let in_row = (opposite.row().cells_set() & x_cells).is_subset(&rect_cells);
+            let in_col = (opposite.col().cells_set() & x_cells).is_subset(&rect_cells);
+
+            if !in_row || !in_col {
+                continue;
+            }
+
+            let elim: Candidate = (opposite, y).into();
+
+            return StrategyResult {
+                eliminations: Set::singleton(elim),
+                highlights: rect.candidates() - Set::singleton(elim),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, basic_board, board_before, candidates,
+    };
+
+    #[test]
+    fn unique_rectangle_1() {
+        let board = basic_board(
+            "...7..59..6.9......45.....3..7.5.6.....3.9.8.2....1..............84..16.59...68..",
+        );
+
+        let result = assert_eliminations(UNIQUE_RECTANGLE_1, &board, "G3#4 G3#6");
+
+        assert_eq!(
+            result.highlights,
+            candidates("E1#4 E1#6 E3#4 E3#6 G1#4 G1#6")
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_2() {
+        let board = basic_board(
+            "..........74..3.8.2....1.3716.....925.9..4..8.2........8..6....7.285...3.......2.",
+        );
+
+        let result = assert_eliminations(UNIQUE_RECTANGLE_2, &board, "E4#7 E5#7 F8#7");
+
+        assert_eq!(
+            result.highlights,
+            candidates("E7#1 E7#6 E8#1 E8#6 H7#1 H7#6 H8#1 H8#6")
+        );
+        assert_eq!(result.highlights2, candidates("E7#7 E8#7"));
+    }
+
+    #[test]
+    fn unique_rectangle_3() {
+        let board = basic_board(
+            "..561......94.7.........45...83.6..17..1....8.3......5.2.58.3........7..6..7....4",
+        );
+
+        // D5 and F5 act as a cell with notes {2, 9}, forming a naked triple
+        // with C5 and I5
+        let result = assert_eliminations(UNIQUE_RECTANGLE_3, &board, "E5#2 E5#9");
+
+        assert_eq!(
+            result.highlights,
+            candidates("D5#4 D5#7 D8#4 D8#7 F5#4 F5#7 F8#4 F8#7")
+        );
+        assert_eq!(
+            result.highlights2,
+            candidates("C5#2 C5#3 C5#9 D5#2 D5#9 F5#2 F5#9 I5#2 I5#3 I5#9")
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_4() {
+        let board = basic_board(
+            "...7..59..6.9......45.....3..7.5.6.....3.9.8.2....1..............84..16.59...68..",
+        );
+
+        let result = assert_eliminations(UNIQUE_RECTANGLE_4, &board, "G2#2 G6#2");
+
+        assert_eq!(
+            result.highlights,
+            candidates("G2#7 G6#7 H2#2 H2#7 H6#2 H6#7")
+        );
+        assert_eq!(result.highlights2, candidates("G2#7 G6#7"));
+    }
+
+    #[test]
+    fn unique_rectangle_5() {
+        let board = board_before(
+            "..1....5........4.....16.78...57.....47..3..6..26.....8.6.54..1.7....9...1.78.4..",
+            UNIQUE_RECTANGLE_5,
+        );
+
+        // three corners have 2 as their only extra note
+        let result = assert_eliminations(UNIQUE_RECTANGLE_5, &board, "B4#2");
+
+        assert_eq!(
+            result.highlights,
+            candidates("A5#3 A5#9 A9#3 A9#9 B5#3 B5#9 B9#3 B9#9")
+        );
+        assert_eq!(result.highlights2, candidates("A5#2 B5#2 B9#2"));
+    }
+
+    #[test]
+    fn unique_rectangle_6() {
+        let board = basic_board(
+            "...7..59..6.9......45.....3..7.5.6.....3.9.8.2....1..............84..16.59...68..",
+        );
+
+        let result = assert_eliminations(UNIQUE_RECTANGLE_6, &board, "C4#8 D6#8");
+
+        assert_eq!(
+            result.highlights,
+            candidates("C4#2 C6#2 C6#8 D4#2 D4#8 D6#2")
+        );
+    }
+
+    #[test]
+    fn hidden_rectangle() {
+        let board = basic_board(
+            ".4....3..6..8.1..7.1..43.8.89..6..7......8.3...7......98...214...1..5....3.......",
+        );
+
+        let result = assert_eliminations(HIDDEN_RECTANGLE, &board, "A4#2");
+
+        assert_eq!(
+            result.highlights,
+            candidates("A4#6 A9#2 A9#6 C4#2 C4#6 C9#2 C9#6")
+        );
+    }
+}
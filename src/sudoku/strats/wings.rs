@@ -0,0 +1,285 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::{
+    bitset::Set,
+    sudoku::{Board, Candidate, Cell, Digit},
+    util::TryIntoArray,
+};
+
+use super::{
+    link::{Bilocal, LinkGraph},
+    Strategy, StrategyResult,
+};
+
+// =============================================================================
+
+pub const W_WING: Strategy = Strategy {
+    name: "W-Wing",
+    difficulty: 150,
+    find: find_w_wing,
+};
+
+pub const XY_WING: Strategy = Strategy {
+    name: "XY-Wing",
+    difficulty: 160,
+    find: |board| find_wing(board, 3, 2..=2, 2..=2),
+};
+
+pub const XYZ_WING: Strategy = Strategy {
+    name: "XYZ-Wing",
+    difficulty: 180,
+    find: |board| find_wing(board, 3, 3..=3, 2..=2),
+};
+
+pub const WXYZ_WING: Strategy = Strategy {
+    name: "WXYZ-Wing",
+    difficulty: 200,
+    find: |board| find_wing(board, 4, 2..=4, 2..=4),
+};
+
+// =============================================================================
+
+/// finds a pivot cell and `size - 1` pincer cells which all see the pivot,
+/// with `size` digits between them.
+///
+/// a digit is restricted if every cell of the wing containing it sees every
+/// other one, so at most one of them can be that digit. if all but one digit
+/// `z` is restricted, then `z` must be in one of the wing cells, since
+/// otherwise `size` cells would be left with `size - 1` digits. so `z` can be
+/// eliminated from any cell which sees every wing cell containing it.
+fn find_wing(
+    board: &Board,
+    size: usize,
+    pivot_sizes: RangeInclusive<usize>,
+    pincer_sizes: RangeInclusive<usize>,
+) -> StrategyResult {
+    for pivot in board.iter_unsolved() {
+        let pivot_notes = *board.get_notes(&pivot).unwrap();
+
+        if !pivot_sizes.contains(&pivot_notes.len()) {
+            continue;
+        }
+
+        let pincer_cells = pivot
+            .iter_neighbors()
+            .filter(|cell| pincer_sizes.contains(&board.count_notes(cell)));
+
+        for pincers in pincer_cells.combinations(size - 1) {
+            let digits: Set<Digit> = pincers
+                .iter()
+                .map(|cell| *board.get_notes(cell).unwrap())
+                .fold(pivot_notes, |acc, notes| acc | notes);
+
+            if digits.len() != size {
+                continue;
+            }
+
+            let wing_cells: Set<Cell> = pincers.iter().copied().chain([pivot]).collect();
+
+            let unrestricted = digits
+                .iter()
+                .filter(|&digit| !is_restricted(wing_cells & board.cells_with_note(digit)))
+                .collect_vec();
+
+            let [z] = unrestricted[..] else { continue };
+
+            let z_cells = wing_cells & board.cells_with_note(z);
+
+            let elim_cells = z_cells
+                .iter()
+                .fold(board.cells_with_note(z), |acc, cell| acc & cell.neighbors());
+
+            if elim_cells.is_empty() {
+                continue;
+            }
+
+            let candidates = |cells: Set<Cell>| -> Set<Candidate> {
+                cells
+                    .iter()
+                    .map(|cell| board.get_notes(&cell).unwrap().map(|d| (cell, d).into()))
+                    .sum()
+            };
+
+            return StrategyResult {
+                eliminations: elim_cells.map(|cell| (cell, z).into()),
+                highlights: candidates(Set::singleton(pivot)),
+                highlights2: candidates(wing_cells - Set::singleton(pivot)),
+                ..Default::default()
+            };
+        }
+    }
+
+    StrategyResult::default()
+}
+
+/// returns `true` if every cell in `cells` sees every other one.
+fn is_restricted(cells: Set<Cell>) -> bool {
+    cells
+        .iter()
+        .tuple_combinations()
+        .all(|(a, b)| a.neighbors().contains(b))
+}
+
+// -----------------------------------------------------------------------------
+
+/// finds two bivalue cells with the same notes `{x, y}` which don't see each
+/// other, along with a strong link on `x` whose ends each see one of them.
+/// if neither cell were `y`, both would be `x`, leaving no `x` for the strong
+/// link. so one of them is `y`, and `y` can be eliminated from any cell which
+/// sees both.
+fn find_w_wing(board: &Board) -> StrategyResult {
+    let strong_links = LinkGraph::new::<Bilocal>(board);
+
+    let bivalue_cells = board
+        .iter_unsolved()
+        .filter(|cell| board.count_notes(cell) == 2)
+        .collect_vec();
+
+    for (p, q) in bivalue_cells.iter().copied().tuple_combinations() {
+        let notes = *board.get_notes(&p).unwrap();
+
+        if board.get_notes(&q) != Some(&notes) || p.neighbors().contains(q) {
+            continue;
+        }
+
+        let Ok([x, y]) = notes.try_into_array() else {
+            continue;
+        };
+
+        for (x, y) in [(x, y), (y, x)] {
+            let elim_cells = p.neighbors() & q.neighbors() & board.cells_with_note(y);
+
+            if elim_cells.is_empty() {
+                continue;
+            }
+
+            for a_cell in p.neighbors() & board.cells_with_note(x) {
+                let a = (a_cell, x).into();
+
+                let Some(b) = strong_links
+                    .neighbors(&a)
+                    .into_iter()
+                    .find(|b| q.neighbors().contains(b.cell()))
+                else {
+                    continue;
+                };
+
+                return StrategyResult {
+                    eliminations: elim_cells.map(|cell| (cell, y).into()),
+                    highlights: [p, q]
+                        .into_iter()
+                        .flat_map(|cell| [(cell, x).into(), (cell, y).into()])
+                        .collect(),
+                    highlights2: Set::from([a, b].as_slice()),
+                    ..Default::default()
+                };
+            }
+        }
+    }
+
+    StrategyResult::default()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::strats::test_util::{
+        assert_eliminations, assert_finds_nothing, basic_board, candidates,
+    };
+
+    #[test]
+    fn w_wing() {
+        let board = basic_board(
+            ".4....3..6..8.1..7.1..43.8.89..6..7......8.3...7......98...214...1..5....3.......",
+        );
+
+        // D7 and E4 are both {2, 5}, and C4 = C7 is a strong link on 5
+        let result = assert_eliminations(W_WING, &board, "E7#2 E9#2");
+
+        assert_eq!(result.highlights, candidates("D7#2 D7#5 E4#2 E4#5"));
+        assert_eq!(result.highlights2, candidates("C4#5 C7#5"));
+    }
+
+    #[test]
+    fn w_wing_without_strong_link() {
+        let board = basic_board(
+            "7...5......8.6..4.4..7...92..7.......6.39..2.1.58..9.......36.......1283.......7.",
+        );
+
+        // A3 and F2 are both {2, 3}, and A2 and B2 see both with a 2, but no
+        // strong link joins them
+        for cell in ["A3", "F2"] {
+            let notes = *board.get_notes(&cell.parse().unwrap()).unwrap();
+            assert_eq!(
+                notes,
+                Set::from(["2", "3"].map(|d| d.parse().unwrap()).as_slice())
+            );
+        }
+
+        assert_finds_nothing(&board, &[W_WING]);
+    }
+
+    #[test]
+    fn xy_wing() {
+        let board = basic_board(
+            "....1..7...9...4..5...9.........31........597.27...8...761..92..347....8..2....4.",
+        );
+
+        let result = assert_eliminations(XY_WING, &board, "E4#6 I5#6");
+
+        assert_eq!(result.highlights, candidates("G5#3 G5#4"));
+        assert_eq!(result.highlights2, candidates("E5#4 E5#6 I4#3 I4#6"));
+    }
+
+    #[test]
+    fn xyz_wing() {
+        let board = basic_board(
+            "....1..7...9...4..5...9.........31........597.27...8...761..92..347....8..2....4.",
+        );
+
+        let result = assert_eliminations(XYZ_WING, &board, "G5#3");
+
+        assert_eq!(result.highlights, candidates("I5#3 I5#6 I5#8"));
+        assert_eq!(result.highlights2, candidates("B5#3 B5#8 I4#3 I4#6"));
+    }
+
+    #[test]
+    fn wxyz_wing() {
+        let board = basic_board(
+            "517.2..9......9.4..8...5.......9.21...3..6.....2....34....5..2..9....7..2...3.9..",
+        );
+
+        let result = assert_eliminations(WXYZ_WING, &board, "C4#7");
+
+        assert_eq!(result.highlights, candidates("C5#1 C5#6 C5#7"));
+        assert_eq!(
+            result.highlights2,
+            candidates("B4#1 B4#7 B4#8 B5#1 B5#7 B5#8 C8#6 C8#7")
+        );
+    }
+
+    #[test]
+    fn wxyz_wing_not_restricted() {
+        let board = basic_board(
+            "........3..7.........1..459.62..3.9..9..85.7.5.37....2..6......9..8..5...3......7",
+        );
+
+        // A1 with A2, A4 and E1 has four digits, but neither the cells with 1
+        // nor the cells with 4 all see each other, so either could be missing
+        let wing: Set<Cell> = ["A1", "A2", "A4", "E1"]
+            .into_iter()
+            .map(|cell| cell.parse().unwrap())
+            .collect();
+
+        for digit in ["1", "4"] {
+            let digit: Digit = digit.parse().unwrap();
+            assert!(!is_restricted(wing & board.cells_with_note(digit)));
+        }
+
+        assert_finds_nothing(&board, &[WXYZ_WING]);
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+use super::{Board, Candidate, Strategy, StrategyResult};
+
+// =============================================================================
+
+/// a deduction which contradicts the known solution of a puzzle
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Violation {
+    pub strategy: &'static str,
+    pub candidate: Candidate,
+    pub kind: ViolationKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ViolationKind {
+    /// the candidate was placed, but it is not in the solution
+    WrongSolution,
+    /// the candidate was eliminated, but it is in the solution
+    TrueElimination,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self.kind {
+            ViolationKind::WrongSolution => "placed wrong candidate",
+            ViolationKind::TrueElimination => "eliminated true candidate",
+        };
+
+        write!(f, "{}: {action} {}", self.strategy, self.candidate)
+    }
+}
+
+// =============================================================================
+
+/// checks the deductions in `result` against `solution`, a fully solved board.
+pub fn verify_result(
+    solution: &Board,
+    strategy: &Strategy,
+    result: &StrategyResult,
+) -> Vec<Violation> {
+    let is_true = |candidate: Candidate| {
+        let (cell, digit) = candidate.as_tuple();
+        solution.get_digit(&cell) == Some(digit)
+    };
+
+    let violation = |candidate, kind| Violation {
+        strategy: strategy.name,
+        candidate,
+        kind,
+    };
+
+    let wrong_solutions = result
+        .solutions
+        .iter()
+        .filter(|&candidate| !is_true(candidate))
+        .map(|candidate| violation(candidate, ViolationKind::WrongSolution));
+
+    let true_eliminations = result
+        .eliminations
+        .iter()
+        .filter(|&candidate| is_true(candidate))
+        .map(|candidate| violation(candidate, ViolationKind::TrueElimination));
+
+    wrong_solutions.chain(true_eliminations).collect()
+}
+
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bitset::Set,
+        sudoku::{unique_solution, Cell, Generator, Solver, STRATEGY_LIST},
+    };
+
+    const PUZZLE: &str =
+        "607005010580007900000060000005000009000936000300000400000080000003600094050200806";
+
+    #[test]
+    fn flags_bad_deductions() {
+        let mut solver = Solver::new();
+        solver.load_board_string(PUZZLE);
+
+        let solution = unique_solution(&solver.board).unwrap();
+
+        // A2 is 9 in the solution
+        let cell: Cell = "A2".parse().unwrap();
+        let true_candidate: Candidate = (cell, "9".parse().unwrap()).into();
+        let false_candidate: Candidate = (cell, "1".parse().unwrap()).into();
+
+        let result = StrategyResult {
+            solutions: Set::singleton(false_candidate),
+            eliminations: Set::singleton(true_candidate),
+            ..Default::default()
+        };
+
+        let violations = verify_result(&solution, &STRATEGY_LIST[0], &result);
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation {
+                    strategy: STRATEGY_LIST[0].name,
+                    candidate: false_candidate,
+                    kind: ViolationKind::WrongSolution,
+                },
+                Violation {
+                    strategy: STRATEGY_LIST[0].name,
+                    candidate: true_candidate,
+                    kind: ViolationKind::TrueElimination,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn solver_finds_no_violations() {
+        let mut solver = Solver::new();
+        solver.set_verify(true);
+        solver.load_board_string(PUZZLE);
+
+        assert!(solver.solve().is_solved());
+        assert_eq!(solver.violations, vec![]);
+    }
+
+    #[test]
+    fn generated_puzzles_have_no_violations() {
+        for seed in 0..20 {
+            let puzzle = Generator::new(seed).generate().unwrap();
+
+            let mut solver = Solver::new();
+            solver.set_verify(true);
+            solver.set_assume_unique(true);
+            solver.load_board(&puzzle);
+            solver.solve();
+
+            assert_eq!(solver.violations, vec![], "puzzle: {puzzle}");
+        }
+    }
+}